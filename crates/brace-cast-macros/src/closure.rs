@@ -0,0 +1,289 @@
+use std::collections::VecDeque;
+
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::Parser;
+use syn::parse::{Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::{bracketed, GenericArgument, Ident, Path, PathArguments, Token, Type};
+
+mod kw {
+    syn::custom_keyword!(from);
+    syn::custom_keyword!(done);
+    syn::custom_keyword!(chained);
+    syn::custom_keyword!(queue);
+    syn::custom_keyword!(params);
+    syn::custom_keyword!(supers);
+}
+
+pub struct Closure {
+    from: Source,
+    done: Vec<Path>,
+    chained: Vec<Path>,
+    queue: Vec<Path>,
+    params: Vec<Ident>,
+    supers: Vec<Path>,
+}
+
+enum Source {
+    Struct(Box<Type>),
+    Trait(Ident),
+}
+
+impl Parse for Closure {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<kw::from>()?;
+        input.parse::<Token![:]>()?;
+        let from = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        input.parse::<kw::done>()?;
+        input.parse::<Token![:]>()?;
+        let done = parse_list(input, Path::parse)?;
+        input.parse::<Token![,]>()?;
+
+        input.parse::<kw::chained>()?;
+        input.parse::<Token![:]>()?;
+        let chained = parse_list(input, Path::parse)?;
+        input.parse::<Token![,]>()?;
+
+        input.parse::<kw::queue>()?;
+        input.parse::<Token![:]>()?;
+        let queue = parse_list(input, Path::parse)?;
+        input.parse::<Token![,]>()?;
+
+        let params = match input.peek(kw::params) {
+            true => {
+                input.parse::<kw::params>()?;
+                input.parse::<Token![:]>()?;
+                let params = parse_list(input, Ident::parse)?;
+                input.parse::<Token![,]>()?;
+                params
+            }
+            false => Vec::new(),
+        };
+
+        let supers = match input.peek(kw::supers) {
+            true => {
+                input.parse::<kw::supers>()?;
+                input.parse::<Token![:]>()?;
+                let supers = parse_list(input, Path::parse)?;
+                input.parse::<Option<Token![,]>>()?;
+                supers
+            }
+            false => Vec::new(),
+        };
+
+        Ok(Self {
+            from,
+            done,
+            chained,
+            queue,
+            params,
+            supers,
+        })
+    }
+}
+
+impl Parse for Source {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![struct]) {
+            input.parse::<Token![struct]>()?;
+
+            return Ok(Source::Struct(Box::new(input.parse()?)));
+        }
+
+        input.parse::<Token![trait]>()?;
+
+        Ok(Source::Trait(input.parse()?))
+    }
+}
+
+impl ToTokens for Source {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Source::Struct(ty) => quote!(struct #ty),
+            Source::Trait(ident) => quote!(trait #ident),
+        });
+    }
+}
+
+fn parse_list<T>(input: ParseStream, parser: fn(ParseStream) -> Result<T>) -> Result<Vec<T>> {
+    let content;
+
+    bracketed!(content in input);
+
+    let list = Punctuated::<T, Token![,]>::parse_terminated_with(&content, parser)?;
+
+    Ok(list.into_iter().collect())
+}
+
+pub fn expand(input: Closure) -> TokenStream {
+    let Closure {
+        from,
+        mut done,
+        mut chained,
+        queue,
+        params,
+        mut supers,
+    } = input;
+
+    // The supertraits come from the descriptor of the last chained trait, spelled relative to
+    // the module that trait was declared in.
+    if let Some(path) = chained.last() {
+        let args = arguments(path);
+
+        for entry in &mut supers {
+            *entry = qualify(&substitute(entry, &params, &args), path);
+        }
+    }
+
+    let mut queue = supers.into_iter().chain(queue).collect::<VecDeque<_>>();
+
+    while let Some(path) = queue.pop_front() {
+        if !contains(&done, &path) {
+            done.push(path.clone());
+        }
+
+        if !contains(&chained, &path) {
+            chained.push(path.clone());
+
+            let queue = queue.iter();
+            let import = descriptor(&path);
+            let name = &import.segments.last().unwrap().ident;
+            let import = match import.segments.len() {
+                1 if import.leading_colon.is_none() => quote!(self::#import),
+                _ => quote!(#import),
+            };
+
+            // A `#[cast]` trait has a descriptor macro under its own name. Importing the path
+            // in the inner block picks it up and shadows the fallback from the outer one.
+            return quote! {
+                const _: () = {
+                    mod __brace_cast_fallback {
+                        #[allow(unused_imports)]
+                        pub(crate) use brace_cast::__brace_cast_leaf as #name;
+                    }
+
+                    #[allow(unused_imports)]
+                    use __brace_cast_fallback::*;
+
+                    const _: () = {
+                        #[allow(unused_imports)]
+                        use #import;
+
+                        #name! {
+                            @closure {
+                                from: #from,
+                                done: [#(#done),*],
+                                chained: [#(#chained),*],
+                                queue: [#(#queue),*],
+                            }
+                        }
+                    };
+                };
+            };
+        }
+    }
+
+    if done.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        brace_cast::impl_cast_as!(#from : #(#done),*);
+    }
+}
+
+// Paths reach the closure spelled relative to different modules, so entries are told apart by
+// their last segment.
+fn contains(list: &[Path], path: &Path) -> bool {
+    let name = |path: &Path| {
+        path.segments
+            .last()
+            .map(|last| last.to_token_stream().to_string())
+    };
+    let path = name(path);
+
+    list.iter().any(|item| name(item) == path)
+}
+
+fn descriptor(path: &Path) -> Path {
+    let mut path = path.clone();
+
+    for segment in &mut path.segments {
+        segment.arguments = PathArguments::None;
+    }
+
+    path
+}
+
+fn qualify(path: &Path, from: &Path) -> Path {
+    let relative = match path.segments.first() {
+        Some(first) => {
+            path.leading_colon.is_none() && (path.segments.len() == 1 || first.ident == "self")
+        }
+        None => false,
+    };
+
+    if !relative || from.segments.len() < 2 {
+        return path.clone();
+    }
+
+    let mut qualified = descriptor(from);
+
+    qualified.segments.pop();
+    qualified.segments.extend(
+        path.segments
+            .iter()
+            .skip_while(|segment| segment.ident == "self")
+            .cloned(),
+    );
+
+    qualified
+}
+
+fn arguments(path: &Path) -> Vec<Type> {
+    match path.segments.last().map(|segment| &segment.arguments) {
+        Some(PathArguments::AngleBracketed(arguments)) => arguments
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn substitute(path: &Path, params: &[Ident], args: &[Type]) -> Path {
+    if params.is_empty() {
+        return path.clone();
+    }
+
+    let tokens = replace(path.to_token_stream(), params, args);
+
+    Path::parse.parse2(tokens).unwrap_or_else(|_| path.clone())
+}
+
+fn replace(tokens: TokenStream, params: &[Ident], args: &[Type]) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(ident) => match params.iter().position(|param| *param == ident) {
+                Some(index) if index < args.len() => args[index].to_token_stream(),
+                _ => TokenTree::Ident(ident).into_token_stream(),
+            },
+            TokenTree::Group(group) => {
+                let stream = replace(group.stream(), params, args);
+                let mut replaced = Group::new(group.delimiter(), stream);
+
+                replaced.set_span(group.span());
+
+                TokenTree::Group(replaced).into_token_stream()
+            }
+            token => token.into_token_stream(),
+        })
+        .collect()
+}
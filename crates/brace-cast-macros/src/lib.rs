@@ -3,18 +3,29 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
 
-use crate::parse::Input;
+use crate::closure::Closure;
+use crate::parse::{Args, Input};
 
+mod closure;
 mod parse;
 mod tagged_impl;
 mod tagged_trait;
 
 #[proc_macro_attribute]
-pub fn cast(_: TokenStream, input: TokenStream) -> TokenStream {
+pub fn cast(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as Args);
     let input = parse_macro_input!(input as Input);
 
     TokenStream::from(match input {
-        Input::Impl(input) => tagged_impl::expand(input),
-        Input::Trait(input) => tagged_trait::expand(input),
+        Input::Impl(input) => tagged_impl::expand(input, args),
+        Input::Trait(input) => tagged_trait::expand(input, args),
     })
 }
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn cast_closure(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Closure);
+
+    TokenStream::from(closure::expand(input))
+}
@@ -1,6 +1,61 @@
 use quote::quote;
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{Attribute, Error, ItemImpl, ItemTrait, Token, Visibility};
+use syn::{Attribute, Error, ItemImpl, ItemTrait, LitStr, Token, Type, Visibility};
+
+mod kw {
+    syn::custom_keyword!(id);
+}
+
+#[derive(Default)]
+pub struct Args {
+    pub supertraits: bool,
+    pub id: Option<Id>,
+    pub types: Vec<Type>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = Args::default();
+
+        while !input.is_empty() {
+            if input.peek(kw::id) {
+                args.id = Some(input.parse()?);
+            } else {
+                let ty: Type = input.parse()?;
+
+                match &ty {
+                    Type::Path(path) if path.path.is_ident("supertraits") => {
+                        args.supertraits = true
+                    }
+                    _ => args.types.push(ty),
+                }
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+pub struct Id {
+    pub token: kw::id,
+    pub name: Option<LitStr>,
+}
+
+impl Parse for Id {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let token = input.parse()?;
+        let name = match input.parse::<Option<Token![=]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+
+        Ok(Id { token, name })
+    }
+}
 
 pub enum Input {
     Trait(ItemTrait),
@@ -1,13 +1,93 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::ItemImpl;
+use syn::{parse_quote, Error, ImplItem, ItemImpl, Path, PathArguments};
 
-pub fn expand(input: ItemImpl) -> TokenStream {
+use crate::parse::Args;
+
+pub fn expand(input: ItemImpl, args: Args) -> TokenStream {
     let from = &input.self_ty;
-    let into = &input.trait_.as_ref().unwrap().1;
+    let into = &object(&input);
+
+    if let Some(id) = &args.id {
+        let msg = "`id` is only supported on traits";
+
+        return Error::new_spanned(id.token, msg).to_compile_error();
+    }
+
+    if !input.generics.params.is_empty() {
+        return expand_generic(&input, args);
+    }
+
+    if let Some(ty) = args.types.first() {
+        let msg = "instantiations are only supported on generic impls";
+
+        return Error::new_spanned(ty, msg).to_compile_error();
+    }
+
+    if args.supertraits {
+        return quote! {
+            #input
+            brace_cast::macros::cast_closure! {
+                from: struct #from,
+                done: [],
+                chained: [],
+                queue: [#into],
+            }
+        };
+    }
 
     quote! {
         #input
         brace_cast::impl_cast_as!(struct #from : #into);
     }
 }
+
+fn expand_generic(input: &ItemImpl, args: Args) -> TokenStream {
+    let from = &input.self_ty;
+    let into = &object(input);
+
+    if args.supertraits {
+        let msg = "`supertraits` is not supported on generic impls";
+
+        return Error::new_spanned(&input.generics, msg).to_compile_error();
+    }
+
+    let params = &input.generics.params;
+    let preds = input
+        .generics
+        .where_clause
+        .as_ref()
+        .map(|clause| &clause.predicates);
+    let types = args.types;
+
+    quote! {
+        #input
+        brace_cast::impl_cast_as!(impl [#params] struct #from : #into where [#preds]);
+        #(
+            brace_cast::register_cast!(struct #types : #into);
+        )*
+    }
+}
+
+fn object(input: &ItemImpl) -> Path {
+    let mut path = input.trait_.as_ref().unwrap().1.clone();
+    let bindings = input.items.iter().filter_map(|item| match item {
+        ImplItem::Type(item) => Some((&item.ident, &item.ty)),
+        _ => None,
+    });
+
+    if let Some(segment) = path.segments.last_mut() {
+        for (ident, ty) in bindings {
+            match &mut segment.arguments {
+                PathArguments::AngleBracketed(arguments) => {
+                    arguments.args.push(parse_quote!(#ident = #ty));
+                }
+                arguments => {
+                    *arguments = PathArguments::AngleBracketed(parse_quote!(<#ident = #ty>))
+                }
+            }
+        }
+    }
+
+    path
+}
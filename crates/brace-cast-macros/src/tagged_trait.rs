@@ -1,21 +1,93 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{parse_quote, ItemTrait, TypeParamBound};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_quote, Error, GenericParam, ItemTrait, Path, TraitBoundModifier, TraitItem,
+    TypeParamBound,
+};
+
+use crate::parse::Args;
+
+const IGNORED: &[&str] = &["Any", "Cast", "Send", "Sized", "Sync", "Unpin"];
 
-pub fn expand(mut input: ItemTrait) -> TokenStream {
+pub fn expand(mut input: ItemTrait, args: Args) -> TokenStream {
     let mut output = TokenStream::new();
     let from = &input.ident;
+    let supers = supertraits(&input);
+    let generic = !input.generics.params.is_empty() || has_associated_types(&input);
 
-    for supertrait in &input.supertraits {
-        if let TypeParamBound::Trait(trait_bound) = supertrait {
-            let into = &trait_bound.path;
+    if args.supertraits && generic {
+        let msg = "`supertraits` is not supported on generic traits";
 
-            output.extend(quote! {
-                brace_cast::impl_cast_as!(trait #from : #into);
-            });
+        return Error::new_spanned(from, msg).to_compile_error();
+    }
+
+    if let Some(id) = &args.id {
+        if generic {
+            let msg = "`id` is not supported on generic traits";
+
+            return Error::new_spanned(id.token, msg).to_compile_error();
+        }
+
+        let name = match &id.name {
+            Some(name) => quote!(#name),
+            None => quote!(concat!(module_path!(), "::", stringify!(#from))),
+        };
+
+        output.extend(quote! {
+            brace_cast::register_interface!(trait #from = #name);
+        });
+    }
+
+    if generic {
+        let (params, object, preds) = object(&input);
+
+        for supertrait in &input.supertraits {
+            if let TypeParamBound::Trait(trait_bound) = supertrait {
+                let into = &trait_bound.path;
+
+                output.extend(quote! {
+                    brace_cast::impl_cast_as!(impl [#params] trait #object : #into where [#preds]);
+                });
+            }
         }
+    } else {
+        output.extend(quote! {
+            brace_cast::macros::cast_closure! {
+                from: trait #from,
+                done: [],
+                chained: [],
+                queue: [#(#supers),*],
+            }
+        });
     }
 
+    // Exported macros share the crate root, so the name is made unique per declaration.
+    let descriptor = format_ident!("__brace_cast_trait_{}_{:016x}", from, fingerprint(&input));
+    let names = input.generics.type_params().map(|param| &param.ident);
+    let supers = supers.iter().map(|path| exported(path));
+    let vis = &input.vis;
+
+    output.extend(quote! {
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #descriptor {
+            (@closure { $($state:tt)* }) => {
+                brace_cast::macros::cast_closure! {
+                    $($state)*
+                    params: [#(#names),*],
+                    supers: [#(#supers),*],
+                }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        #vis use #descriptor as #from;
+    });
+
     input.supertraits.push(parse_quote!(brace_cast::Cast));
 
     quote! {
@@ -23,3 +95,112 @@ pub fn expand(mut input: ItemTrait) -> TokenStream {
         #output
     }
 }
+
+fn supertraits(input: &ItemTrait) -> Vec<&Path> {
+    input
+        .supertraits
+        .iter()
+        .filter_map(|supertrait| match supertrait {
+            TypeParamBound::Trait(bound) => match bound.modifier {
+                TraitBoundModifier::None => Some(&bound.path),
+                TraitBoundModifier::Maybe(_) => None,
+            },
+            _ => None,
+        })
+        .filter(|path| match path.segments.last() {
+            Some(segment) => !IGNORED.iter().any(|name| segment.ident == name),
+            None => false,
+        })
+        .collect()
+}
+
+fn fingerprint(input: &ItemTrait) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    let span = input.ident.span();
+
+    input.to_token_stream().to_string().hash(&mut hasher);
+    span.file().hash(&mut hasher);
+    span.start().line.hash(&mut hasher);
+    span.start().column.hash(&mut hasher);
+    hasher.finish()
+}
+
+// The descriptor is expanded in other modules and crates, so paths from the crate root go
+// through `$crate`.
+fn exported(path: &Path) -> TokenStream {
+    match path.segments.first() {
+        Some(first) if first.ident == "crate" => {
+            let rest = path.segments.iter().skip(1);
+
+            quote!($crate #(:: #rest)*)
+        }
+        _ => quote!(#path),
+    }
+}
+
+fn has_associated_types(input: &ItemTrait) -> bool {
+    input
+        .items
+        .iter()
+        .any(|item| matches!(item, TraitItem::Type(_)))
+}
+
+fn object(input: &ItemTrait) -> (TokenStream, TokenStream, TokenStream) {
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+
+    for param in &input.generics.params {
+        let mut param = param.clone();
+
+        match &mut param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+
+                args.push(quote!(#ident));
+                param.eq_token = None;
+                param.default = None;
+            }
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+
+                args.push(quote!(#lifetime));
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+
+                args.push(quote!(#ident));
+                param.eq_token = None;
+                param.default = None;
+            }
+        }
+
+        params.push(quote!(#param));
+    }
+
+    for item in &input.items {
+        if let TraitItem::Type(item) = item {
+            let ident = &item.ident;
+            let param = format_ident!("__{}", ident);
+            let bounds = &item.bounds;
+
+            args.push(quote!(#ident = #param));
+            params.push(match bounds.is_empty() {
+                true => quote!(#param),
+                false => quote!(#param: #bounds),
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let preds = input
+        .generics
+        .where_clause
+        .as_ref()
+        .map(|clause| &clause.predicates);
+
+    (
+        quote!(#(#params),*),
+        quote!(#ident<#(#args),*>),
+        quote!(#preds),
+    )
+}
@@ -0,0 +1,20 @@
+use brace_cast::{cast, Cast};
+
+pub const CREATE_SYMBOL: &[u8] = b"brace_cast_test_create";
+
+pub type Create = fn(name: &str) -> Box<dyn Animal>;
+
+#[cast(id = "brace.test.Animal/1")]
+pub trait Animal: Cast {
+    fn name(&self) -> &str;
+}
+
+#[cast(id = "brace.test.Mammal/1")]
+pub trait Mammal: Animal {
+    fn legs(&self) -> usize;
+}
+
+#[cast(id)]
+pub trait Groom: Cast {
+    fn groom(&mut self) -> usize;
+}
@@ -0,0 +1,41 @@
+use brace_cast::{cast, impl_cast_as, Cast};
+use brace_cast_test_api::{Animal, Mammal};
+
+#[cast(supertraits)]
+trait Pet: Mammal {
+    fn owner(&self) -> &str;
+}
+
+struct Dog;
+
+impl Animal for Dog {
+    fn name(&self) -> &str {
+        "Rex"
+    }
+}
+
+impl Mammal for Dog {
+    fn legs(&self) -> usize {
+        4
+    }
+}
+
+impl Pet for Dog {
+    fn owner(&self) -> &str {
+        "Sam"
+    }
+}
+
+impl_cast_as!(struct Dog: Animal, Mammal, Pet);
+
+#[test]
+fn test_foreign_supertrait_closure() {
+    let dog: Box<dyn Pet> = Box::new(Dog);
+
+    assert_eq!(dog.cast_ref::<dyn Mammal>().unwrap().legs(), 4);
+    assert_eq!(dog.cast_ref::<dyn Animal>().unwrap().name(), "Rex");
+
+    let dog: Box<dyn Animal> = Box::new(Dog);
+
+    assert_eq!(dog.cast_ref::<dyn Pet>().unwrap().owner(), "Sam");
+}
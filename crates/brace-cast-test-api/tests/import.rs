@@ -0,0 +1,55 @@
+mod support;
+
+use std::any::type_name;
+
+use brace_cast::registry::{self, CastExportsFn, CAST_EXPORTS_SYMBOL};
+use brace_cast::Cast;
+use brace_cast_test_api::{Create, Groom, Mammal, CREATE_SYMBOL};
+use libloading::Symbol;
+
+#[test]
+fn test_plugin_import() {
+    let library = support::load_plugin();
+    let create: Symbol<Create> = unsafe { library.get(CREATE_SYMBOL) }.unwrap();
+    let exports: Symbol<CastExportsFn> = unsafe { library.get(CAST_EXPORTS_SYMBOL) }.unwrap();
+    let mut cat = create("Felix");
+
+    assert!(cat.cast_ref::<dyn Mammal>().is_none());
+    assert!(registry::cast_from_mut::<_, dyn Groom>(&mut *cat).is_none());
+
+    let exports = exports();
+    let import = unsafe { registry::import(exports) };
+
+    assert_eq!(import.ref_records(), exports.ref_records());
+    assert_eq!(import.mut_records(), exports.mut_records());
+    assert!(import
+        .records()
+        .any(|view| view.target_name() == type_name::<dyn Groom>()));
+    assert_eq!(cat.cast_ref::<dyn Mammal>().unwrap().legs(), 4);
+
+    let groom = registry::cast_from_mut::<_, dyn Groom>(&mut *cat).unwrap();
+
+    assert_eq!(groom.groom(), 1);
+
+    let again = unsafe { registry::import(exports) };
+
+    assert_eq!(again.ref_records(), 0);
+    assert_eq!(again.mut_records(), 0);
+
+    drop(again);
+
+    assert!(cat.cast_ref::<dyn Mammal>().is_some());
+
+    drop(import);
+
+    assert!(cat.cast_ref::<dyn Mammal>().is_none());
+    assert!(registry::cast_from_mut::<_, dyn Groom>(&mut *cat).is_none());
+
+    let reload = unsafe { registry::import(exports) };
+
+    assert_eq!(reload.ref_records(), exports.ref_records());
+    assert_eq!(cat.cast_ref::<dyn Mammal>().unwrap().legs(), 4);
+
+    drop(reload);
+    drop(cat);
+}
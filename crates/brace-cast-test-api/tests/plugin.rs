@@ -0,0 +1,27 @@
+mod support;
+
+use brace_cast::{registry, Cast};
+use brace_cast_test_api::{Create, Groom, Mammal, CREATE_SYMBOL};
+use libloading::Symbol;
+
+#[test]
+fn test_plugin_interface_query() {
+    let library = support::load_plugin();
+    let create: Symbol<Create> = unsafe { library.get(CREATE_SYMBOL) }.unwrap();
+    let mut cat = create("Felix");
+
+    assert_eq!(cat.name(), "Felix");
+    assert!(cat.cast_ref::<dyn Mammal>().is_none());
+
+    let mammal = registry::cast_interface_ref::<_, dyn Mammal>(&*cat).unwrap();
+
+    assert_eq!(mammal.name(), "Felix");
+    assert_eq!(mammal.legs(), 4);
+
+    let groom = registry::cast_interface_mut::<_, dyn Groom>(&mut *cat).unwrap();
+
+    assert_eq!(groom.groom(), 1);
+    assert_eq!(groom.groom(), 2);
+
+    drop(cat);
+}
@@ -0,0 +1,16 @@
+use std::env;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+
+use libloading::Library;
+
+pub fn load_plugin() -> &'static Library {
+    // The plugin is a dev-dependency, so cargo builds it next to this test binary against the
+    // same `brace-cast` as the host.
+    let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let file = format!("{}brace_cast_test_plugin{}", DLL_PREFIX, DLL_SUFFIX);
+    let library = unsafe { Library::new(deps.join(file)) }.unwrap();
+
+    // Imported casts borrow the plugin's code for the rest of the process, so it is never
+    // unloaded.
+    Box::leak(Box::new(library))
+}
@@ -0,0 +1,28 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use brace_cast::ffi::CastHeader;
+use brace_cast_test_api::{Animal, Groom, Mammal};
+
+fn main() {
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let header = CastHeader::new()
+        .interface::<dyn Animal>("ANIMAL")
+        .interface::<dyn Mammal>("MAMMAL")
+        .interface::<dyn Groom>("GROOM")
+        .vtable(
+            "BraceCastMammalVtable",
+            &["size_t (*legs)(const BraceCastObject *object)"],
+        );
+
+    fs::write(out.join("brace_cast.h"), header.to_string()).unwrap();
+
+    cc::Build::new()
+        .file("c/host.c")
+        .include(&out)
+        .warnings_into_errors(true)
+        .compile("brace_cast_test_host");
+
+    println!("cargo:rerun-if-changed=c/host.c");
+}
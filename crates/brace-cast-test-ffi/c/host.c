@@ -0,0 +1,39 @@
+#include <stddef.h>
+
+#include "brace_cast.h"
+
+int host_supports(const BraceCastObject *object, uint64_t iid) {
+    return brace_cast_supports(object, iid);
+}
+
+unsigned host_interfaces(const BraceCastObject *object) {
+    unsigned mask = 0;
+
+    if (brace_cast_supports(object, BRACE_CAST_IID_ANIMAL)) {
+        mask |= 1;
+    }
+
+    if (brace_cast_supports(object, BRACE_CAST_IID_MAMMAL)) {
+        mask |= 2;
+    }
+
+    if (brace_cast_supports(object, BRACE_CAST_IID_GROOM)) {
+        mask |= 4;
+    }
+
+    return mask;
+}
+
+const void *host_query_mammal(const BraceCastObject *object) {
+    return brace_cast_query(object, BRACE_CAST_IID_MAMMAL);
+}
+
+size_t host_mammal_legs(const BraceCastObject *object) {
+    const BraceCastMammalVtable *mammal = brace_cast_query(object, BRACE_CAST_IID_MAMMAL);
+
+    return mammal ? mammal->legs(object) : 0;
+}
+
+void host_release(BraceCastObject *object) {
+    brace_cast_release(object);
+}
@@ -0,0 +1,30 @@
+use std::ffi::c_void;
+use std::os::raw::{c_int, c_uint};
+
+use brace_cast::ffi::{self, CastHandle, CastObject};
+use brace_cast_test_api::Mammal;
+
+pub static MAMMAL_VTABLE: MammalVtable = MammalVtable { legs: mammal_legs };
+
+#[repr(C)]
+pub struct MammalVtable {
+    pub legs: unsafe extern "C" fn(*const CastHandle) -> usize,
+}
+
+extern "C" {
+    pub fn host_supports(handle: *const CastHandle, iid: u64) -> c_int;
+    pub fn host_interfaces(handle: *const CastHandle) -> c_uint;
+    pub fn host_query_mammal(handle: *const CastHandle) -> *const c_void;
+    pub fn host_mammal_legs(handle: *const CastHandle) -> usize;
+    pub fn host_release(handle: *mut CastHandle);
+}
+
+pub fn register() {
+    ffi::register_vtable::<dyn Mammal, _>(&MAMMAL_VTABLE);
+}
+
+unsafe extern "C" fn mammal_legs(handle: *const CastHandle) -> usize {
+    CastObject::from_handle_ref(handle)
+        .and_then(|object| object.query::<dyn Mammal>())
+        .map_or(0, |mammal| mammal.legs())
+}
@@ -0,0 +1,51 @@
+use std::ptr;
+
+use brace_cast::ffi::CastObject;
+use brace_cast::{cast, CastInterface};
+use brace_cast_test_api::{Animal, Groom, Mammal};
+use brace_cast_test_ffi::{
+    host_interfaces, host_mammal_legs, host_query_mammal, host_release, host_supports,
+    MAMMAL_VTABLE,
+};
+
+struct Dog {
+    name: String,
+}
+
+#[cast]
+impl Animal for Dog {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cast]
+impl Mammal for Dog {
+    fn legs(&self) -> usize {
+        4
+    }
+}
+
+#[test]
+fn test_c_host_query() {
+    let dog = CastObject::new(Dog {
+        name: "Rover".into(),
+    });
+    let handle = dog.into_handle();
+
+    unsafe {
+        assert_eq!(host_supports(handle, <dyn Animal>::ID.to_raw()), 1);
+        assert_eq!(host_supports(handle, <dyn Groom>::ID.to_raw()), 0);
+        assert_eq!(host_supports(ptr::null(), <dyn Animal>::ID.to_raw()), 0);
+        assert_eq!(host_interfaces(handle), 0b011);
+        assert_eq!(host_mammal_legs(handle), 0);
+
+        brace_cast_test_ffi::register();
+
+        assert!(ptr::eq(host_query_mammal(handle).cast(), &MAMMAL_VTABLE));
+        assert_eq!(host_mammal_legs(handle), 4);
+        assert_eq!(host_mammal_legs(ptr::null()), 0);
+
+        host_release(handle);
+    }
+}
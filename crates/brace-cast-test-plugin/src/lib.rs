@@ -0,0 +1,39 @@
+use brace_cast::cast;
+use brace_cast_test_api::{Animal, Groom, Mammal};
+
+struct Cat {
+    name: String,
+    groomed: usize,
+}
+
+#[cast]
+impl Animal for Cat {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cast]
+impl Mammal for Cat {
+    fn legs(&self) -> usize {
+        4
+    }
+}
+
+#[cast]
+impl Groom for Cat {
+    fn groom(&mut self) -> usize {
+        self.groomed += 1;
+        self.groomed
+    }
+}
+
+#[no_mangle]
+pub fn brace_cast_test_create(name: &str) -> Box<dyn Animal> {
+    Box::new(Cat {
+        name: name.into(),
+        groomed: 0,
+    })
+}
+
+brace_cast::export_casts!();
@@ -0,0 +1,98 @@
+// The ref path of the registry before per-source tables, kept as-is so the bench compares against
+// what shipped: one `RwLock<HashMap>` keyed by `(target, source)` and boxed handlers behind
+// `dyn Any`.
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
+use brace_cast::registry::CastRefHandler;
+use brace_cast::{Cast, CastAsRef, CastError, CastErrorKind};
+
+pub struct CastRefRecord(TypeId, TypeId, Option<Box<dyn Any + Sync>>);
+
+impl CastRefRecord {
+    pub fn new<S, T>(handler: CastRefHandler<T>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        let handler = Box::new(handler) as Box<dyn Any + Sync>;
+
+        Self(TypeId::of::<S>(), TypeId::of::<T>(), Some(handler))
+    }
+
+    fn key(&self) -> (TypeId, TypeId) {
+        (self.1, self.0)
+    }
+
+    fn handler(&self) -> Option<&dyn Any> {
+        self.2.as_deref().map(|handler| handler as &dyn Any)
+    }
+}
+
+#[derive(Default)]
+pub struct CastRefRegistry(RwLock<HashMap<(TypeId, TypeId), &'static CastRefRecord>>);
+
+impl CastRefRegistry {
+    pub fn register<S, T>(&self)
+    where
+        S: CastAsRef<T> + 'static,
+        T: ?Sized + 'static,
+    {
+        let record = CastRefRecord::new::<S, T>(|item| {
+            let item: &S = item.downcast_ref()?;
+
+            item.cast_as_ref()
+        });
+        let record: &'static CastRefRecord = Box::leak(Box::new(record));
+        let mut map = self.0.write().unwrap_or_else(PoisonError::into_inner);
+
+        map.insert(record.key(), record);
+    }
+
+    pub fn cast_from_ref<'a, S, T>(&self, from: &'a S) -> Option<&'a T>
+    where
+        S: Cast + ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        self.cast(from.cast_type_name(), from.cast_as_any_ref())
+            .ok()
+    }
+
+    fn cast<'a, T>(&self, source: &'static str, from: &'a dyn Any) -> Result<&'a T, CastError>
+    where
+        T: ?Sized + 'static,
+    {
+        let type_id = from.type_id();
+        let rec = {
+            let map = self.0.read().unwrap_or_else(PoisonError::into_inner);
+
+            map.get(&(TypeId::of::<T>(), type_id)).copied()
+        };
+
+        if let Some(rec) = rec {
+            let handler = rec
+                .handler()
+                .and_then(|item| item.downcast_ref::<CastRefHandler<T>>());
+
+            if let Some(cast) = handler {
+                if let Some(item) = (cast)(from) {
+                    return Ok(item);
+                }
+
+                return Err(CastError::new(
+                    source,
+                    type_name::<T>(),
+                    CastErrorKind::Mismatch,
+                ));
+            }
+        }
+
+        Err(CastError::new(
+            source,
+            type_name::<T>(),
+            CastErrorKind::Unregistered,
+        ))
+    }
+}
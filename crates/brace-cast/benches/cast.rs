@@ -0,0 +1,78 @@
+use brace_cast::registry;
+use brace_cast::{impl_cast_as, Cast};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use self::baseline::CastRefRegistry;
+
+mod baseline;
+
+trait Animal: Cast {}
+
+trait Mammal: Animal {}
+
+trait Feline: Mammal {}
+
+trait Canine: Mammal {}
+
+struct Cat;
+
+impl Animal for Cat {}
+
+impl Mammal for Cat {}
+
+impl Feline for Cat {}
+
+impl_cast_as!(struct Cat: Animal, Mammal, Feline);
+impl_cast_as!(trait Animal: Mammal, Feline, Canine);
+
+fn baseline() -> CastRefRegistry {
+    let registry = CastRefRegistry::default();
+
+    registry.register::<Cat, dyn Animal>();
+    registry.register::<Cat, dyn Mammal>();
+    registry.register::<Cat, dyn Feline>();
+
+    registry
+}
+
+fn bench_cast_ref(c: &mut Criterion) {
+    let cat: Box<dyn Animal> = Box::new(Cat);
+    let baseline = baseline();
+    let mut group = c.benchmark_group("cast_ref");
+
+    registry::cast_from_ref::<dyn Animal, dyn Mammal>(&*cat).unwrap();
+    baseline
+        .cast_from_ref::<dyn Animal, dyn Mammal>(&*cat)
+        .unwrap();
+
+    group.bench_function("cast_table", |b| {
+        b.iter(|| registry::cast_from_ref::<dyn Animal, dyn Mammal>(black_box(&*cat)))
+    });
+
+    group.bench_function("baseline", |b| {
+        b.iter(|| baseline.cast_from_ref::<dyn Animal, dyn Mammal>(black_box(&*cat)))
+    });
+
+    group.bench_function("cast_table_miss", |b| {
+        b.iter(|| registry::cast_from_ref::<dyn Animal, dyn Canine>(black_box(&*cat)))
+    });
+
+    group.bench_function("baseline_miss", |b| {
+        b.iter(|| baseline.cast_from_ref::<dyn Animal, dyn Canine>(black_box(&*cat)))
+    });
+
+    group.finish();
+}
+
+fn bench_cast_box(c: &mut Criterion) {
+    c.bench_function("cast_box", |b| {
+        b.iter_batched(
+            || Box::new(Cat) as Box<dyn Animal>,
+            |cat| registry::cast_from_box::<dyn Animal, dyn Feline>(black_box(cat)),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_cast_ref, bench_cast_box);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CastErrorKind {
+    Unregistered,
+    Mismatch,
+    NotUnique,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CastError {
+    source: &'static str,
+    target: &'static str,
+    kind: CastErrorKind,
+}
+
+impl CastError {
+    pub fn new(source: &'static str, target: &'static str, kind: CastErrorKind) -> Self {
+        Self {
+            source,
+            target,
+            kind,
+        }
+    }
+
+    pub fn source_name(&self) -> &'static str {
+        self.source
+    }
+
+    pub fn target_name(&self) -> &'static str {
+        self.target
+    }
+
+    pub fn kind(&self) -> CastErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            CastErrorKind::Unregistered => write!(
+                f,
+                "no cast registered from `{}` to `{}`",
+                self.source, self.target
+            ),
+            CastErrorKind::Mismatch => write!(
+                f,
+                "cannot cast `{}` to `{}`: concrete type mismatch",
+                self.source, self.target
+            ),
+            CastErrorKind::NotUnique => write!(
+                f,
+                "cannot cast `{}` to `{}`: shared pointer is not unique",
+                self.source, self.target
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CastError {}
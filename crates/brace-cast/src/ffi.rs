@@ -0,0 +1,288 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ffi::{c_int, c_void};
+use core::fmt;
+use core::ptr;
+
+use crate::registry;
+use crate::sync::{Lazy, RwLock};
+use crate::{Cast, CastInterface, InterfaceId};
+
+static CAST_VTABLES: Lazy<RwLock<VtableMap>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+const PRELUDE: &str = "#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern \"C\" {
+#endif
+
+typedef struct BraceCastObject BraceCastObject;
+";
+
+const FUNCTIONS: &str = "int brace_cast_supports(const BraceCastObject *object, uint64_t iid);
+const void *brace_cast_query(const BraceCastObject *object, uint64_t iid);
+void brace_cast_release(BraceCastObject *object);
+
+#ifdef __cplusplus
+}
+#endif
+";
+
+/// Returns whether the object behind `handle` implements the interface `iid`.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle returned by [`CastObject::into_handle`].
+#[no_mangle]
+pub unsafe extern "C" fn brace_cast_supports(handle: *const CastHandle, iid: u64) -> c_int {
+    match CastObject::from_handle_ref(handle) {
+        Some(object) => object.supports(InterfaceId::from_raw(iid)) as c_int,
+        None => 0,
+    }
+}
+
+/// Returns the function table registered with [`register_vtable`] for `iid`, or null if the
+/// object behind `handle` does not implement that interface or no table is registered.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle returned by [`CastObject::into_handle`].
+#[no_mangle]
+pub unsafe extern "C" fn brace_cast_query(handle: *const CastHandle, iid: u64) -> *const c_void {
+    match CastObject::from_handle_ref(handle) {
+        Some(object) => object.query_vtable(InterfaceId::from_raw(iid)),
+        None => ptr::null(),
+    }
+}
+
+/// # Safety
+///
+/// `handle` must be null or a live handle returned by [`CastObject::into_handle`]. It must not be
+/// used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn brace_cast_release(handle: *mut CastHandle) {
+    if !handle.is_null() {
+        drop(CastObject::from_handle(handle));
+    }
+}
+
+/// Registers the `#[repr(C)]` function table that [`brace_cast_query`] returns for `T`.
+///
+/// The functions take the object's handle rather than a data pointer, so one table serves every
+/// type that implements `T`; they can reach the object through [`CastObject::from_handle_ref`].
+pub fn register_vtable<T, V>(vtable: &'static V)
+where
+    T: CastInterface + ?Sized,
+    V: Sync,
+{
+    let mut vtables = CAST_VTABLES.write();
+
+    match vtables.iter_mut().find(|(id, _)| *id == T::ID) {
+        Some(entry) => entry.1 = vtable,
+        None => vtables.push((T::ID, vtable)),
+    }
+}
+
+pub fn vtable(id: InterfaceId) -> *const c_void {
+    let vtables = CAST_VTABLES.read();
+
+    match vtables.iter().find(|(entry, _)| *entry == id) {
+        Some((_, vtable)) => *vtable as *const dyn Sync as *const c_void,
+        None => ptr::null(),
+    }
+}
+
+#[repr(C)]
+pub struct CastHandle {
+    _private: [u8; 0],
+}
+
+pub struct CastObject(Box<dyn Cast>);
+
+impl CastObject {
+    pub fn new<T>(object: T) -> Box<Self>
+    where
+        T: Cast + 'static,
+    {
+        Self::from_box(Box::new(object))
+    }
+
+    pub fn from_box(object: Box<dyn Cast>) -> Box<Self> {
+        Box::new(Self(object))
+    }
+
+    pub fn into_handle(self: Box<Self>) -> *mut CastHandle {
+        Box::into_raw(self).cast()
+    }
+
+    /// # Safety
+    ///
+    /// `handle` must be a live handle returned by [`CastObject::into_handle`]. It must not be
+    /// used again afterwards.
+    pub unsafe fn from_handle(handle: *mut CastHandle) -> Box<Self> {
+        Box::from_raw(handle.cast())
+    }
+
+    /// # Safety
+    ///
+    /// `handle` must be null or a live handle returned by [`CastObject::into_handle`] that
+    /// outlives `'a`.
+    pub unsafe fn from_handle_ref<'a>(handle: *const CastHandle) -> Option<&'a Self> {
+        handle.cast::<Self>().as_ref()
+    }
+
+    pub fn into_inner(self) -> Box<dyn Cast> {
+        self.0
+    }
+
+    pub fn get(&self) -> &dyn Cast {
+        &*self.0
+    }
+
+    pub fn query<T>(&self) -> Option<&T>
+    where
+        T: CastInterface + ?Sized + 'static,
+    {
+        registry::cast_interface_ref(&*self.0)
+    }
+
+    pub fn supports(&self, id: InterfaceId) -> bool {
+        !(*self.0).cast_query_ptr(id).is_null()
+    }
+
+    pub fn query_vtable(&self, id: InterfaceId) -> *const c_void {
+        match self.supports(id) {
+            true => vtable(id),
+            false => ptr::null(),
+        }
+    }
+}
+
+pub struct CastHeader {
+    guard: String,
+    interfaces: Vec<(String, InterfaceId)>,
+    vtables: Vec<(String, Vec<String>)>,
+}
+
+impl CastHeader {
+    pub fn new() -> Self {
+        Self {
+            guard: "BRACE_CAST_H".to_string(),
+            interfaces: Vec::new(),
+            vtables: Vec::new(),
+        }
+    }
+
+    pub fn guard(mut self, guard: &str) -> Self {
+        self.guard = guard.to_string();
+        self
+    }
+
+    pub fn interface<T>(self, name: &str) -> Self
+    where
+        T: CastInterface + ?Sized,
+    {
+        self.interface_id(name, T::ID)
+    }
+
+    pub fn interface_id(mut self, name: &str, id: InterfaceId) -> Self {
+        self.interfaces.push((name.to_string(), id));
+        self
+    }
+
+    pub fn vtable(mut self, name: &str, functions: &[&str]) -> Self {
+        let functions = functions.iter().map(|function| function.to_string());
+
+        self.vtables.push((name.to_string(), functions.collect()));
+        self
+    }
+
+    pub fn registered(mut self) -> Self {
+        let mut records = registry::interfaces()
+            .filter(|record| self.interfaces.iter().all(|(_, id)| *id != record.id()))
+            .map(|record| (constant_name(record.target_name()), record.id()))
+            .collect::<Vec<_>>();
+        let names = self
+            .interfaces
+            .iter()
+            .chain(&records)
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+
+        // Distinct paths can still mangle to one name, so a shared name gets the id appended.
+        for (name, id) in &mut records {
+            if names.iter().filter(|other| *other == name).count() > 1 {
+                *name = format!("{}_{:016X}", name, id.to_raw());
+            }
+        }
+
+        records.sort();
+        self.interfaces.extend(records);
+        self
+    }
+}
+
+impl Default for CastHeader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CastHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "#ifndef {}", self.guard)?;
+        writeln!(f, "#define {}", self.guard)?;
+        writeln!(f)?;
+        writeln!(f, "{}", PRELUDE)?;
+
+        for (name, id) in &self.interfaces {
+            writeln!(f, "#define BRACE_CAST_IID_{} UINT64_C(0x{})", name, id)?;
+        }
+
+        if !self.interfaces.is_empty() {
+            writeln!(f)?;
+        }
+
+        for (name, functions) in &self.vtables {
+            writeln!(f, "typedef struct {} {{", name)?;
+
+            for function in functions {
+                writeln!(f, "    {};", function)?;
+            }
+
+            writeln!(f, "}} {};", name)?;
+            writeln!(f)?;
+        }
+
+        writeln!(f, "{}", FUNCTIONS)?;
+        writeln!(f, "#endif /* {} */", self.guard)
+    }
+}
+
+type VtableMap = Vec<(InterfaceId, &'static dyn Sync)>;
+
+fn constant_name(target_name: &str) -> String {
+    let mut constant = String::new();
+    let mut boundary = false;
+
+    // The whole path takes part, generics included, so `dyn Handler<app::Click>` and
+    // `dyn Handler<web::Click>` stay apart; every run of punctuation becomes one `_`.
+    for c in target_name.trim_start_matches("dyn ").chars() {
+        if c.is_ascii_uppercase() && boundary {
+            constant.push('_');
+        }
+
+        boundary = c.is_ascii_lowercase() || c.is_ascii_digit();
+
+        if c.is_ascii_alphanumeric() {
+            constant.push(c.to_ascii_uppercase());
+        } else if !constant.is_empty() && !constant.ends_with('_') {
+            constant.push('_');
+        }
+    }
+
+    constant.trim_end_matches('_').to_string()
+}
@@ -1,13 +1,36 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 extern crate self as brace_cast;
 
-use std::any::Any;
-use std::rc::Rc;
-use std::sync::Arc;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+use core::any::{type_name, Any, TypeId};
 
 pub use brace_cast_macros::cast;
+#[cfg(feature = "alloc")]
 pub use inventory;
+#[cfg(feature = "linkme")]
+pub use linkme;
+
+pub use self::error::{CastError, CastErrorKind};
+pub use self::interface::{CastInterface, InterfaceId};
+
+mod error;
+mod interface;
+#[cfg(feature = "alloc")]
+mod sync;
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod macros;
+#[cfg(feature = "alloc")]
 pub mod registry;
 
 pub fn cast_ref<T, U>(item: &U) -> Option<&T>
@@ -26,6 +49,85 @@ where
     item.cast_as_mut()
 }
 
+pub fn try_cast_ref<T, U>(item: &U) -> Result<&T, CastError>
+where
+    T: ?Sized,
+    U: CastAsRef<T> + ?Sized,
+{
+    item.try_cast_as_ref()
+}
+
+pub fn try_cast_mut<T, U>(item: &mut U) -> Result<&mut T, CastError>
+where
+    T: ?Sized,
+    U: CastAsMut<T> + ?Sized,
+{
+    item.try_cast_as_mut()
+}
+
+#[cfg(feature = "alloc")]
+pub fn cast_box<T, U>(item: Box<U>) -> Result<Box<T>, Box<U>>
+where
+    T: ?Sized,
+    U: CastAsBox<T> + ?Sized,
+{
+    item.cast_as_box()
+}
+
+#[cfg(feature = "alloc")]
+pub fn cast_rc<T, U>(item: Rc<U>) -> Result<Rc<T>, Rc<U>>
+where
+    T: ?Sized,
+    U: CastAsRc<T> + ?Sized,
+{
+    item.cast_as_rc()
+}
+
+#[cfg(feature = "alloc")]
+pub fn cast_arc<T, U>(item: Arc<U>) -> Result<Arc<T>, Arc<U>>
+where
+    T: ?Sized,
+    U: CastAsArc<T> + ?Sized,
+{
+    item.cast_as_arc()
+}
+
+#[cfg(feature = "alloc")]
+pub fn cast_rc_mut<T, U>(item: &mut Rc<U>) -> Result<&mut T, CastError>
+where
+    T: ?Sized,
+    U: CastAsRef<T> + CastAsMut<T> + ?Sized,
+{
+    (**item).try_cast_as_ref()?;
+
+    match Rc::get_mut(item) {
+        Some(item) => item.try_cast_as_mut(),
+        None => Err(CastError::new(
+            type_name::<Rc<U>>(),
+            type_name::<T>(),
+            CastErrorKind::NotUnique,
+        )),
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub fn cast_arc_mut<T, U>(item: &mut Arc<U>) -> Result<&mut T, CastError>
+where
+    T: ?Sized,
+    U: CastAsRef<T> + CastAsMut<T> + ?Sized,
+{
+    (**item).try_cast_as_ref()?;
+
+    match Arc::get_mut(item) {
+        Some(item) => item.try_cast_as_mut(),
+        None => Err(CastError::new(
+            type_name::<Arc<U>>(),
+            type_name::<T>(),
+            CastErrorKind::NotUnique,
+        )),
+    }
+}
+
 pub trait Cast: CastAsAny {
     fn cast_ref<T>(&self) -> Option<&T>
     where
@@ -36,6 +138,16 @@ pub trait Cast: CastAsAny {
     where
         T: ?Sized,
         Self: CastAsMut<T> + Sized;
+
+    fn try_cast_ref<T>(&self) -> Result<&T, CastError>
+    where
+        T: ?Sized,
+        Self: CastAsRef<T> + Sized;
+
+    fn try_cast_mut<T>(&mut self) -> Result<&mut T, CastError>
+    where
+        T: ?Sized,
+        Self: CastAsMut<T> + Sized;
 }
 
 impl<T> Cast for T {
@@ -54,9 +166,50 @@ impl<T> Cast for T {
     {
         self.cast_as_mut()
     }
+
+    fn try_cast_ref<U>(&self) -> Result<&U, CastError>
+    where
+        U: ?Sized,
+        Self: CastAsRef<U>,
+    {
+        self.try_cast_as_ref()
+    }
+
+    fn try_cast_mut<U>(&mut self) -> Result<&mut U, CastError>
+    where
+        U: ?Sized,
+        Self: CastAsMut<U>,
+    {
+        self.try_cast_as_mut()
+    }
 }
 
 pub trait CastAsAny {
+    fn cast_type_name(&self) -> &'static str;
+
+    #[doc(hidden)]
+    fn cast_type_id(&self) -> TypeId
+    where
+        Self: 'static;
+
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    unsafe fn cast_query_ref(&self, id: InterfaceId, out: *mut ()) -> bool
+    where
+        Self: 'static;
+
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    unsafe fn cast_query_mut(&mut self, id: InterfaceId, out: *mut ()) -> bool
+    where
+        Self: 'static;
+
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    fn cast_query_ptr(&self, id: InterfaceId) -> *const ()
+    where
+        Self: 'static;
+
     fn cast_as_any_ref(&self) -> &dyn Any
     where
         Self: 'static;
@@ -64,9 +217,59 @@ pub trait CastAsAny {
     fn cast_as_any_mut(&mut self) -> &mut dyn Any
     where
         Self: 'static;
+
+    #[cfg(feature = "alloc")]
+    fn cast_as_any_box(self: Box<Self>) -> Box<dyn Any>
+    where
+        Self: 'static;
+
+    #[cfg(feature = "alloc")]
+    fn cast_as_any_rc(self: Rc<Self>) -> Rc<dyn Any>
+    where
+        Self: 'static;
+
+    #[cfg(feature = "alloc")]
+    fn cast_as_any_arc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>
+    where
+        Self: Send + Sync + 'static;
 }
 
 impl<T> CastAsAny for T {
+    fn cast_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn cast_type_id(&self) -> TypeId
+    where
+        T: 'static,
+    {
+        TypeId::of::<T>()
+    }
+
+    #[cfg(feature = "alloc")]
+    unsafe fn cast_query_ref(&self, id: InterfaceId, out: *mut ()) -> bool
+    where
+        T: 'static,
+    {
+        registry::query_ref(self, id, out)
+    }
+
+    #[cfg(feature = "alloc")]
+    unsafe fn cast_query_mut(&mut self, id: InterfaceId, out: *mut ()) -> bool
+    where
+        T: 'static,
+    {
+        registry::query_mut(self, id, out)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn cast_query_ptr(&self, id: InterfaceId) -> *const ()
+    where
+        T: 'static,
+    {
+        registry::query_ptr(self, id)
+    }
+
     fn cast_as_any_ref(&self) -> &dyn Any
     where
         T: 'static,
@@ -80,10 +283,44 @@ impl<T> CastAsAny for T {
     {
         self
     }
+
+    #[cfg(feature = "alloc")]
+    fn cast_as_any_box(self: Box<Self>) -> Box<dyn Any>
+    where
+        T: 'static,
+    {
+        self
+    }
+
+    #[cfg(feature = "alloc")]
+    fn cast_as_any_rc(self: Rc<Self>) -> Rc<dyn Any>
+    where
+        T: 'static,
+    {
+        self
+    }
+
+    #[cfg(feature = "alloc")]
+    fn cast_as_any_arc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>
+    where
+        T: Send + Sync + 'static,
+    {
+        self
+    }
 }
 
 pub trait CastAsRef<T: ?Sized> {
     fn cast_as_ref(&self) -> Option<&T>;
+
+    fn try_cast_as_ref(&self) -> Result<&T, CastError> {
+        self.cast_as_ref().ok_or_else(|| {
+            CastError::new(
+                type_name::<Self>(),
+                type_name::<T>(),
+                CastErrorKind::Mismatch,
+            )
+        })
+    }
 }
 
 impl<T, U> CastAsRef<U> for T
@@ -94,10 +331,24 @@ where
     fn cast_as_ref(&self) -> Option<&U> {
         CastFromRef::cast_from_ref(self)
     }
+
+    fn try_cast_as_ref(&self) -> Result<&U, CastError> {
+        CastFromRef::try_cast_from_ref(self)
+    }
 }
 
 pub trait CastAsMut<T: ?Sized> {
     fn cast_as_mut(&mut self) -> Option<&mut T>;
+
+    fn try_cast_as_mut(&mut self) -> Result<&mut T, CastError> {
+        self.cast_as_mut().ok_or_else(|| {
+            CastError::new(
+                type_name::<Self>(),
+                type_name::<T>(),
+                CastErrorKind::Mismatch,
+            )
+        })
+    }
 }
 
 impl<T, U> CastAsMut<U> for T
@@ -108,59 +359,222 @@ where
     fn cast_as_mut(&mut self) -> Option<&mut U> {
         CastFromMut::cast_from_mut(self)
     }
+
+    fn try_cast_as_mut(&mut self) -> Result<&mut U, CastError> {
+        CastFromMut::try_cast_from_mut(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastAsBox<T: ?Sized> {
+    fn cast_as_box(self: Box<Self>) -> Result<Box<T>, Box<Self>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> CastAsBox<U> for T
+where
+    T: ?Sized,
+    U: CastFromBox<T> + ?Sized,
+{
+    fn cast_as_box(self: Box<Self>) -> Result<Box<U>, Box<Self>> {
+        CastFromBox::cast_from_box(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastAsRc<T: ?Sized> {
+    fn cast_as_rc(self: Rc<Self>) -> Result<Rc<T>, Rc<Self>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> CastAsRc<U> for T
+where
+    T: ?Sized,
+    U: CastFromRc<T> + ?Sized,
+{
+    fn cast_as_rc(self: Rc<Self>) -> Result<Rc<U>, Rc<Self>> {
+        CastFromRc::cast_from_rc(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastAsArc<T: ?Sized> {
+    fn cast_as_arc(self: Arc<Self>) -> Result<Arc<T>, Arc<Self>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> CastAsArc<U> for T
+where
+    T: ?Sized,
+    U: CastFromArc<T> + ?Sized,
+{
+    fn cast_as_arc(self: Arc<Self>) -> Result<Arc<U>, Arc<Self>> {
+        CastFromArc::cast_from_arc(self)
+    }
 }
 
 pub trait CastFromRef<T: ?Sized> {
     fn cast_from_ref(from: &T) -> Option<&Self>;
+
+    fn try_cast_from_ref(from: &T) -> Result<&Self, CastError> {
+        Self::cast_from_ref(from).ok_or_else(|| {
+            CastError::new(
+                type_name::<T>(),
+                type_name::<Self>(),
+                CastErrorKind::Mismatch,
+            )
+        })
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, U> CastFromRef<Box<U>> for T
 where
-    T: ?Sized,
-    U: CastAsRef<T> + ?Sized,
+    T: ?Sized + 'static,
+    U: Cast + ?Sized + 'static,
 {
     fn cast_from_ref(from: &Box<U>) -> Option<&Self> {
-        (**from).cast_as_ref()
+        registry::cast_from_ref(&**from)
+    }
+
+    fn try_cast_from_ref(from: &Box<U>) -> Result<&Self, CastError> {
+        registry::try_cast_from_ref(&**from)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, U> CastFromRef<Rc<U>> for T
 where
-    T: ?Sized,
-    U: CastAsRef<T> + ?Sized,
+    T: ?Sized + 'static,
+    U: Cast + ?Sized + 'static,
 {
     fn cast_from_ref(from: &Rc<U>) -> Option<&Self> {
-        (**from).cast_as_ref()
+        registry::cast_from_ref(&**from)
+    }
+
+    fn try_cast_from_ref(from: &Rc<U>) -> Result<&Self, CastError> {
+        registry::try_cast_from_ref(&**from)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, U> CastFromRef<Arc<U>> for T
 where
-    T: ?Sized,
-    U: CastAsRef<T> + ?Sized,
+    T: ?Sized + 'static,
+    U: Cast + ?Sized + 'static,
 {
     fn cast_from_ref(from: &Arc<U>) -> Option<&Self> {
-        (**from).cast_as_ref()
+        registry::cast_from_ref(&**from)
+    }
+
+    fn try_cast_from_ref(from: &Arc<U>) -> Result<&Self, CastError> {
+        registry::try_cast_from_ref(&**from)
     }
 }
 
 pub trait CastFromMut<T: ?Sized> {
     fn cast_from_mut(from: &mut T) -> Option<&mut Self>;
+
+    fn try_cast_from_mut(from: &mut T) -> Result<&mut Self, CastError> {
+        Self::cast_from_mut(from).ok_or_else(|| {
+            CastError::new(
+                type_name::<T>(),
+                type_name::<Self>(),
+                CastErrorKind::Mismatch,
+            )
+        })
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<T, U> CastFromMut<Box<U>> for T
 where
-    T: ?Sized,
-    U: CastAsMut<T> + ?Sized,
+    T: ?Sized + 'static,
+    U: Cast + ?Sized + 'static,
 {
     fn cast_from_mut(from: &mut Box<U>) -> Option<&mut Self> {
-        (**from).cast_as_mut()
+        registry::cast_from_mut(&mut **from)
+    }
+
+    fn try_cast_from_mut(from: &mut Box<U>) -> Result<&mut Self, CastError> {
+        registry::try_cast_from_mut(&mut **from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> CastFromMut<Rc<U>> for T
+where
+    T: ?Sized + 'static,
+    U: Cast + ?Sized + 'static,
+{
+    fn cast_from_mut(from: &mut Rc<U>) -> Option<&mut Self> {
+        registry::cast_from_mut(Rc::get_mut(from)?)
+    }
+
+    fn try_cast_from_mut(from: &mut Rc<U>) -> Result<&mut Self, CastError> {
+        match Rc::get_mut(from) {
+            Some(item) => registry::try_cast_from_mut(item),
+            None => Err(CastError::new(
+                type_name::<Rc<U>>(),
+                type_name::<Self>(),
+                CastErrorKind::NotUnique,
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, U> CastFromMut<Arc<U>> for T
+where
+    T: ?Sized + 'static,
+    U: Cast + ?Sized + 'static,
+{
+    fn cast_from_mut(from: &mut Arc<U>) -> Option<&mut Self> {
+        registry::cast_from_mut(Arc::get_mut(from)?)
     }
+
+    fn try_cast_from_mut(from: &mut Arc<U>) -> Result<&mut Self, CastError> {
+        match Arc::get_mut(from) {
+            Some(item) => registry::try_cast_from_mut(item),
+            None => Err(CastError::new(
+                type_name::<Arc<U>>(),
+                type_name::<Self>(),
+                CastErrorKind::NotUnique,
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastFromBox<T: ?Sized> {
+    fn cast_from_box(from: Box<T>) -> Result<Box<Self>, Box<T>>;
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastFromRc<T: ?Sized> {
+    fn cast_from_rc(from: Rc<T>) -> Result<Rc<Self>, Rc<T>>;
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastFromArc<T: ?Sized> {
+    fn cast_from_arc(from: Arc<T>) -> Result<Arc<Self>, Arc<T>>;
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use crate::{cast, cast_mut, cast_ref, impl_cast_as, impl_cast_from, Cast};
+    use std::any::{type_name, Any, TypeId};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use crate::registry::{
+        self, CastArcRecord, CastArcRegistry, CastBoxRecord, CastBoxRegistry, CastMutRegistry,
+        CastParityReport, CastRcRecord, CastRcRegistry, CastRefRecord, CastRefRegistry,
+    };
+    use crate::{
+        cast, cast_arc, cast_arc_mut, cast_box, cast_mut, cast_rc, cast_rc_mut, cast_ref,
+        impl_cast_as, impl_cast_from, try_cast_mut, try_cast_ref, Cast, CastErrorKind, CastFromBox,
+        CastFromMut, CastFromRc, CastFromRef, CastInterface, InterfaceId,
+    };
 
     trait Animal: Cast {
         fn name(&self) -> &str;
@@ -305,46 +719,399 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_cast_struct_as_trait_object() {
-        let mut cat = Cat::new("Felix");
-
-        assert!(cat.cast_ref::<dyn Animal>().is_some());
-        assert!(cat.cast_ref::<dyn Mammal>().is_some());
-        assert!(cat.cast_ref::<dyn Feline>().is_some());
-        assert!(cat.cast_mut::<dyn Animal>().is_some());
-        assert!(cat.cast_mut::<dyn Mammal>().is_some());
-        assert!(cat.cast_mut::<dyn Feline>().is_some());
+    struct Mouse {
+        name: Rc<str>,
+        legs: usize,
+        tail: bool,
+    }
 
-        let mut dog = Dog::new("Rover");
+    impl Mouse {
+        fn new<S>(name: S) -> Self
+        where
+            S: Into<Rc<str>>,
+        {
+            Self {
+                name: name.into(),
+                legs: 4,
+                tail: true,
+            }
+        }
+    }
 
-        assert!(dog.cast_ref::<dyn Animal>().is_some());
-        assert!(dog.cast_ref::<dyn Mammal>().is_some());
-        assert!(dog.cast_ref::<dyn Canine>().is_some());
-        assert!(dog.cast_mut::<dyn Animal>().is_some());
-        assert!(dog.cast_mut::<dyn Mammal>().is_some());
-        assert!(dog.cast_mut::<dyn Canine>().is_some());
+    impl Animal for Mouse {
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
 
-        let mut rat = Rat::new("Daisy");
+    impl Mammal for Mouse {
+        fn legs(&self) -> &usize {
+            &self.legs
+        }
+    }
 
-        assert!(rat.cast_ref::<dyn Animal>().is_some());
-        assert!(rat.cast_ref::<dyn Mammal>().is_some());
-        assert!(rat.cast_ref::<dyn Rodent>().is_some());
-        assert!(rat.cast_mut::<dyn Animal>().is_some());
-        assert!(rat.cast_mut::<dyn Mammal>().is_some());
-        assert!(rat.cast_mut::<dyn Rodent>().is_some());
+    impl Rodent for Mouse {
+        fn tail(&self) -> &bool {
+            &self.tail
+        }
     }
 
-    #[test]
-    fn test_cast_trait_object_box_as_struct() {
-        let mut cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+    impl_cast_as!(struct Mouse: Animal, Mammal, Rodent);
 
-        assert!(cat.cast_ref::<Cat>().is_some());
-        assert!(cat.cast_ref::<Dog>().is_none());
-        assert!(cat.cast_ref::<Rat>().is_none());
-        assert!(cat.cast_mut::<Cat>().is_some());
-        assert!(cat.cast_mut::<Dog>().is_none());
-        assert!(cat.cast_mut::<Rat>().is_none());
+    #[cast]
+    trait Pet {
+        fn owner(&self) -> &str;
+    }
+
+    #[cast(supertraits)]
+    trait Working: Pet {
+        fn job(&self) -> &str;
+    }
+
+    #[cast(supertraits)]
+    trait Herder: Working {
+        fn flock(&self) -> &usize;
+    }
+
+    trait Tagged: Cast {
+        fn tag(&self) -> &str;
+    }
+
+    #[cast]
+    trait Shepherd: Herder + Tagged {
+        fn whistle(&self) -> &str;
+    }
+
+    struct Collie {
+        owner: String,
+        flock: usize,
+    }
+
+    impl Pet for Collie {
+        fn owner(&self) -> &str {
+            &self.owner
+        }
+    }
+
+    impl Working for Collie {
+        fn job(&self) -> &str {
+            "herding"
+        }
+    }
+
+    impl Herder for Collie {
+        fn flock(&self) -> &usize {
+            &self.flock
+        }
+    }
+
+    impl Tagged for Collie {
+        fn tag(&self) -> &str {
+            "collie"
+        }
+    }
+
+    impl Shepherd for Collie {
+        fn whistle(&self) -> &str {
+            "come by"
+        }
+    }
+
+    impl_cast_as!(struct Collie: Pet, Working, Herder, Tagged, Shepherd);
+
+    struct Kelpie {
+        owner: String,
+    }
+
+    impl Pet for Kelpie {
+        fn owner(&self) -> &str {
+            &self.owner
+        }
+    }
+
+    impl Working for Kelpie {
+        fn job(&self) -> &str {
+            "droving"
+        }
+    }
+
+    #[cast(supertraits)]
+    impl Herder for Kelpie {
+        fn flock(&self) -> &usize {
+            &120
+        }
+    }
+
+    struct Cage<T> {
+        name: String,
+        legs: usize,
+        tag: T,
+    }
+
+    impl<T> Cage<T> {
+        fn new<S>(name: S, tag: T) -> Self
+        where
+            S: Into<String>,
+        {
+            Self {
+                name: name.into(),
+                legs: 4,
+                tag,
+            }
+        }
+    }
+
+    #[cast(Cage<u32>, Cage<String>)]
+    impl<T> Animal for Cage<T>
+    where
+        T: 'static,
+    {
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    #[cast(Cage<u32>)]
+    impl<T: 'static> Mammal for Cage<T> {
+        fn legs(&self) -> &usize {
+            &self.legs
+        }
+    }
+
+    struct Hamster {
+        name: String,
+        legs: usize,
+    }
+
+    impl Hamster {
+        fn new<S>(name: S) -> Self
+        where
+            S: Into<String>,
+        {
+            Self {
+                name: name.into(),
+                legs: 4,
+            }
+        }
+    }
+
+    impl Animal for Hamster {
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    impl Mammal for Hamster {
+        fn legs(&self) -> &usize {
+            &self.legs
+        }
+    }
+
+    impl CastFromRef<Hamster> for dyn Animal {
+        fn cast_from_ref(from: &Hamster) -> Option<&Self> {
+            Some(from)
+        }
+    }
+
+    impl CastFromMut<Hamster> for dyn Animal {
+        fn cast_from_mut(from: &mut Hamster) -> Option<&mut Self> {
+            Some(from)
+        }
+    }
+
+    impl CastFromBox<Hamster> for dyn Animal {
+        fn cast_from_box(from: Box<Hamster>) -> Result<Box<Self>, Box<Hamster>> {
+            Ok(from)
+        }
+    }
+
+    impl CastFromRc<Hamster> for dyn Animal {
+        fn cast_from_rc(from: Rc<Hamster>) -> Result<Rc<Self>, Rc<Hamster>> {
+            Ok(from)
+        }
+    }
+
+    struct Click;
+
+    struct Press;
+
+    #[cast]
+    trait Listener<E>: Cast {
+        fn listen(&self, event: &E) -> &str;
+    }
+
+    #[cast]
+    trait Handler<E>: Listener<E> {
+        fn handle(&self, event: &E) -> usize;
+    }
+
+    #[cast]
+    trait Litter: Animal {
+        type Pup;
+
+        fn next_pup(&mut self) -> Option<Self::Pup>;
+    }
+
+    struct Button {
+        clicks: usize,
+    }
+
+    #[cast(supertraits)]
+    impl Handler<Click> for Button {
+        fn handle(&self, _: &Click) -> usize {
+            self.clicks
+        }
+    }
+
+    impl Listener<Click> for Button {
+        fn listen(&self, _: &Click) -> &str {
+            "click"
+        }
+    }
+
+    #[cast]
+    impl Handler<Press> for Button {
+        fn handle(&self, _: &Press) -> usize {
+            self.clicks * 2
+        }
+    }
+
+    #[cast]
+    impl Listener<Press> for Button {
+        fn listen(&self, _: &Press) -> &str {
+            "press"
+        }
+    }
+
+    struct Ferret {
+        name: Rc<str>,
+    }
+
+    #[cast]
+    impl Animal for Ferret {
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    struct Burrow {
+        name: String,
+        pups: u8,
+    }
+
+    #[cast]
+    impl Animal for Burrow {
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    #[cast]
+    impl Litter for Burrow {
+        type Pup = u8;
+
+        fn next_pup(&mut self) -> Option<u8> {
+            self.pups = self.pups.checked_sub(1)?;
+
+            Some(self.pups)
+        }
+    }
+
+    #[cast(id = "brace.test.Grazer/1")]
+    trait Grazer: Cast {
+        fn pasture(&self) -> &str;
+    }
+
+    #[cast(id)]
+    trait Rider: Cast {
+        fn saddled(&mut self) -> &mut bool;
+    }
+
+    mod paddock {
+        use crate::{cast, Cast};
+
+        #[cast(id)]
+        pub trait Rider: Cast {}
+    }
+
+    struct Horse {
+        pasture: String,
+        saddled: bool,
+    }
+
+    #[cast]
+    impl Grazer for Horse {
+        fn pasture(&self) -> &str {
+            &self.pasture
+        }
+    }
+
+    #[cast]
+    impl Rider for Horse {
+        fn saddled(&mut self) -> &mut bool {
+            &mut self.saddled
+        }
+    }
+
+    #[cfg(feature = "static-registry")]
+    crate::cast_table! {
+        trait Grazer, Rider, paddock::Rider, static_table::Mammal;
+        Cat: Animal, Mammal, Feline;
+        Dog: Animal, Mammal, Canine;
+        Rat: Animal, Mammal, Rodent;
+        Mouse: Animal, Mammal, Rodent;
+        Collie: Pet, Working, Herder, Tagged, Shepherd;
+        Kelpie: Pet, Working, Herder;
+        Cage<u32>: Animal, Mammal;
+        Cage<String>: Animal;
+        Button: Listener<Click>, Handler<Click>, Listener<Press>, Handler<Press>;
+        Ferret: Animal;
+        Burrow: Animal, Litter<Pup = u8>;
+        Horse: Grazer, Rider;
+        static_table::Cat: static_table::Animal, static_table::Mammal;
+        static_table::Dog: static_table::Animal, static_table::Mammal, static_table::Canine;
+    }
+
+    #[test]
+    fn test_cast_struct_as_trait_object() {
+        let mut cat = Cat::new("Felix");
+
+        assert!(cat.cast_ref::<dyn Animal>().is_some());
+        assert!(cat.cast_ref::<dyn Mammal>().is_some());
+        assert!(cat.cast_ref::<dyn Feline>().is_some());
+        assert!(cat.cast_mut::<dyn Animal>().is_some());
+        assert!(cat.cast_mut::<dyn Mammal>().is_some());
+        assert!(cat.cast_mut::<dyn Feline>().is_some());
+
+        let mut dog = Dog::new("Rover");
+
+        assert!(dog.cast_ref::<dyn Animal>().is_some());
+        assert!(dog.cast_ref::<dyn Mammal>().is_some());
+        assert!(dog.cast_ref::<dyn Canine>().is_some());
+        assert!(dog.cast_mut::<dyn Animal>().is_some());
+        assert!(dog.cast_mut::<dyn Mammal>().is_some());
+        assert!(dog.cast_mut::<dyn Canine>().is_some());
+
+        let mut rat = Rat::new("Daisy");
+
+        assert!(rat.cast_ref::<dyn Animal>().is_some());
+        assert!(rat.cast_ref::<dyn Mammal>().is_some());
+        assert!(rat.cast_ref::<dyn Rodent>().is_some());
+        assert!(rat.cast_mut::<dyn Animal>().is_some());
+        assert!(rat.cast_mut::<dyn Mammal>().is_some());
+        assert!(rat.cast_mut::<dyn Rodent>().is_some());
+    }
+
+    #[test]
+    fn test_cast_trait_object_box_as_struct() {
+        let mut cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+
+        assert!(cat.cast_ref::<Cat>().is_some());
+        assert!(cat.cast_ref::<Dog>().is_none());
+        assert!(cat.cast_ref::<Rat>().is_none());
+        assert!(cat.cast_mut::<Cat>().is_some());
+        assert!(cat.cast_mut::<Dog>().is_none());
+        assert!(cat.cast_mut::<Rat>().is_none());
 
         let mut cat: Box<dyn Mammal> = Box::new(Cat::new("Felix"));
 
@@ -676,4 +1443,1186 @@ mod tests {
         assert!(cast_mut::<dyn Animal, _>(rat).is_some());
         assert!(cast_mut::<dyn Mammal, _>(rat).is_some());
     }
+
+    #[test]
+    fn test_cast_trait_object_box_into_struct() {
+        let cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+        let cat = cast_box::<Dog, _>(cat).err().unwrap();
+        let cat = cast_box::<Rat, _>(cat).err().unwrap();
+        let cat = cast_box::<Cat, _>(cat).ok().unwrap();
+
+        assert_eq!(cat.name, "Felix");
+
+        let dog: Box<dyn Mammal> = Box::new(Dog::new("Rover"));
+        let dog = cast_box::<Cat, _>(dog).err().unwrap();
+        let dog = cast_box::<Rat, _>(dog).err().unwrap();
+        let dog = cast_box::<Dog, _>(dog).ok().unwrap();
+
+        assert_eq!(dog.name, "Rover");
+
+        let rat: Box<dyn Rodent> = Box::new(Rat::new("Daisy"));
+        let rat = cast_box::<Rat, _>(rat).ok().unwrap();
+
+        assert_eq!(rat.name, "Daisy");
+    }
+
+    #[test]
+    fn test_cast_trait_object_box_into_trait_object() {
+        let cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+        let cat = cast_box::<dyn Canine, _>(cat).err().unwrap();
+        let cat = cast_box::<dyn Rodent, _>(cat).err().unwrap();
+        let cat = cast_box::<dyn Feline, _>(cat).ok().unwrap();
+        let cat = cast_box::<dyn Mammal, _>(cat).ok().unwrap();
+
+        assert_eq!(cat.name(), "Felix");
+        assert_eq!(cat.legs(), &4);
+
+        let dog: Box<dyn Animal> = Box::new(Dog::new("Rover"));
+        let dog = cast_box::<dyn Feline, _>(dog).err().unwrap();
+        let dog = cast_box::<dyn Canine, _>(dog).ok().unwrap();
+        let dog = cast_box::<dyn Animal, _>(dog).ok().unwrap();
+
+        assert_eq!(dog.name(), "Rover");
+
+        let rat: Box<dyn Mammal> = Box::new(Rat::new("Daisy"));
+        let rat = cast_box::<dyn Feline, _>(rat).err().unwrap();
+        let rat = cast_box::<dyn Rodent, _>(rat).ok().unwrap();
+
+        assert_eq!(rat.tail(), &true);
+    }
+
+    #[test]
+    fn test_cast_trait_object_rc_into_struct() {
+        let cat: Rc<dyn Animal> = Rc::new(Cat::new("Felix"));
+        let other = cat.clone();
+        let cat = cast_rc::<Dog, _>(cat).err().unwrap();
+        let cat = cast_rc::<Cat, _>(cat).ok().unwrap();
+
+        assert_eq!(cat.name, "Felix");
+        assert_eq!(Rc::strong_count(&other), 2);
+
+        let dog: Rc<dyn Canine> = Rc::new(Dog::new("Rover"));
+        let dog = cast_rc::<Dog, _>(dog).ok().unwrap();
+
+        assert_eq!(dog.name, "Rover");
+
+        let mouse: Rc<dyn Mammal> = Rc::new(Mouse::new("Jerry"));
+        let mouse = cast_rc::<Rat, _>(mouse).err().unwrap();
+        let mouse = cast_rc::<Mouse, _>(mouse).ok().unwrap();
+
+        assert_eq!(&*mouse.name, "Jerry");
+    }
+
+    #[test]
+    fn test_cast_trait_object_rc_into_trait_object() {
+        let cat: Rc<dyn Animal> = Rc::new(Cat::new("Felix"));
+        let other = cat.clone();
+        let cat = cast_rc::<dyn Canine, _>(cat).err().unwrap();
+        let cat = cast_rc::<dyn Feline, _>(cat).ok().unwrap();
+
+        assert_eq!(cat.eyes(), &2);
+        assert_eq!(Rc::strong_count(&other), 2);
+
+        let dog: Rc<dyn Mammal> = Rc::new(Dog::new("Rover"));
+        let dog = cast_rc::<dyn Canine, _>(dog).ok().unwrap();
+
+        assert_eq!(dog.ears(), &2);
+
+        let mouse: Rc<dyn Animal> = Rc::new(Mouse::new("Jerry"));
+        let mouse = cast_rc::<dyn Feline, _>(mouse).err().unwrap();
+        let mouse = cast_rc::<dyn Rodent, _>(mouse).ok().unwrap();
+
+        assert_eq!(mouse.tail(), &true);
+    }
+
+    #[test]
+    fn test_cast_trait_object_arc_into_struct() {
+        let cat: Arc<dyn Animal + Send + Sync> = Arc::new(Cat::new("Felix"));
+        let other = cat.clone();
+        let cat = cast_arc::<Dog, _>(cat).err().unwrap();
+        let cat = cast_arc::<Cat, _>(cat).ok().unwrap();
+
+        assert_eq!(cat.name, "Felix");
+        assert_eq!(Arc::strong_count(&other), 2);
+
+        let dog: Arc<dyn Canine + Send + Sync> = Arc::new(Dog::new("Rover"));
+        let dog = cast_arc::<Dog, _>(dog).ok().unwrap();
+
+        assert_eq!(dog.name, "Rover");
+
+        let rat: Arc<dyn Rodent + Send + Sync> = Arc::new(Rat::new("Daisy"));
+        let rat = cast_arc::<Rat, _>(rat).ok().unwrap();
+
+        assert_eq!(rat.name, "Daisy");
+    }
+
+    #[test]
+    fn test_cast_trait_object_arc_into_trait_object() {
+        let cat: Arc<dyn Animal + Send + Sync> = Arc::new(Cat::new("Felix"));
+        let other = cat.clone();
+        let cat = cast_arc::<dyn Canine + Send + Sync, _>(cat).err().unwrap();
+        let cat = cast_arc::<dyn Feline + Send + Sync, _>(cat).ok().unwrap();
+
+        assert_eq!(cat.eyes(), &2);
+        assert_eq!(Arc::strong_count(&other), 2);
+
+        let dog: Arc<dyn Animal + Send + Sync> = Arc::new(Dog::new("Rover"));
+        let dog = cast_arc::<dyn Canine + Send + Sync, _>(dog).ok().unwrap();
+        let dog = cast_arc::<dyn Mammal + Send + Sync, _>(dog).ok().unwrap();
+
+        assert_eq!(dog.legs(), &4);
+
+        let rat: Arc<dyn Mammal + Send + Sync> = Arc::new(Rat::new("Daisy"));
+        let rat = cast_arc::<dyn Feline + Send + Sync, _>(rat).err().unwrap();
+        let rat = cast_arc::<dyn Rodent + Send + Sync, _>(rat).ok().unwrap();
+
+        assert_eq!(rat.tail(), &true);
+    }
+
+    #[test]
+    fn test_cast_trait_object_rc_as_unique() {
+        let mut cat: Rc<dyn Animal> = Rc::new(Cat::new("Felix"));
+
+        assert!(cat.cast_mut::<Cat>().is_some());
+        assert!(cat.cast_mut::<Dog>().is_none());
+        assert!(cat.cast_mut::<dyn Feline>().is_some());
+        assert!(cat.cast_mut::<dyn Canine>().is_none());
+
+        let other = cat.clone();
+
+        assert!(cat.cast_ref::<Cat>().is_some());
+        assert!(cat.cast_mut::<Cat>().is_none());
+        assert!(cat.cast_mut::<dyn Feline>().is_none());
+
+        drop(other);
+
+        assert!(cat.cast_mut::<Cat>().is_some());
+
+        let mut dog: Arc<dyn Mammal> = Arc::new(Dog::new("Rover"));
+
+        assert!(dog.cast_mut::<Dog>().is_some());
+        assert!(dog.cast_mut::<Cat>().is_none());
+
+        let other = dog.clone();
+
+        assert!(dog.cast_mut::<Dog>().is_none());
+
+        drop(other);
+
+        assert!(dog.cast_mut::<Dog>().is_some());
+    }
+
+    #[test]
+    fn test_cast_trait_object_rc_as_shared() {
+        let mut cat: Rc<dyn Animal> = Rc::new(Cat::new("Felix"));
+
+        assert!(cast_rc_mut::<Cat, _>(&mut cat).is_ok());
+        assert!(cast_rc_mut::<dyn Feline, _>(&mut cat).is_ok());
+        assert_eq!(
+            cast_rc_mut::<Dog, _>(&mut cat)
+                .map_err(|err| err.kind())
+                .err(),
+            Some(CastErrorKind::Mismatch)
+        );
+
+        let other = cat.clone();
+
+        assert_eq!(
+            cast_rc_mut::<Cat, _>(&mut cat)
+                .map_err(|err| err.kind())
+                .err(),
+            Some(CastErrorKind::NotUnique)
+        );
+        assert_eq!(
+            cast_rc_mut::<dyn Canine, _>(&mut cat)
+                .map_err(|err| err.kind())
+                .err(),
+            Some(CastErrorKind::Unregistered)
+        );
+
+        drop(other);
+
+        let mut dog: Arc<dyn Mammal> = Arc::new(Dog::new("Rover"));
+        let other = dog.clone();
+
+        assert_eq!(
+            cast_arc_mut::<Dog, _>(&mut dog)
+                .map_err(|err| err.kind())
+                .err(),
+            Some(CastErrorKind::NotUnique)
+        );
+        assert_eq!(
+            cast_arc_mut::<Rat, _>(&mut dog)
+                .map_err(|err| err.kind())
+                .err(),
+            Some(CastErrorKind::Mismatch)
+        );
+
+        drop(other);
+
+        assert!(cast_arc_mut::<Dog, _>(&mut dog).is_ok());
+    }
+
+    #[test]
+    fn test_try_cast_trait_object() {
+        let mut cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+
+        assert!(cat.try_cast_ref::<Cat>().is_ok());
+        assert!(cat.try_cast_ref::<dyn Feline>().is_ok());
+        assert!(cat.try_cast_mut::<Cat>().is_ok());
+        assert!(cat.try_cast_mut::<dyn Feline>().is_ok());
+
+        let err = cat.try_cast_ref::<Dog>().err().unwrap();
+
+        assert_eq!(err.kind(), CastErrorKind::Mismatch);
+        assert_eq!(err.source_name(), type_name::<Cat>());
+        assert_eq!(err.target_name(), type_name::<Dog>());
+
+        let err = try_cast_mut::<dyn Canine, _>(&mut *cat).err().unwrap();
+
+        assert_eq!(err.kind(), CastErrorKind::Unregistered);
+        assert_eq!(err.source_name(), type_name::<Cat>());
+        assert_eq!(err.target_name(), type_name::<dyn Canine>());
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "no cast registered from `{}` to `{}`",
+                type_name::<Cat>(),
+                type_name::<dyn Canine>()
+            )
+        );
+
+        let rat = Rat::new("Daisy");
+        let rat: &dyn Rodent = try_cast_ref::<dyn Rodent, _>(&rat).ok().unwrap();
+        let err = try_cast_ref::<Mouse, _>(rat).err().unwrap();
+
+        assert_eq!(err.kind(), CastErrorKind::Mismatch);
+        assert_eq!(err.source_name(), type_name::<Rat>());
+    }
+
+    #[test]
+    fn test_registry_record_views() {
+        let registry = CastRefRegistry::new();
+        let view = registry
+            .records()
+            .find(|view| {
+                view.source_id() == TypeId::of::<Cat>()
+                    && view.target_id() == TypeId::of::<dyn Feline>()
+            })
+            .unwrap();
+
+        assert_eq!(view.source_name(), type_name::<Cat>());
+        assert_eq!(view.target_name(), type_name::<dyn Feline>());
+        assert_eq!(
+            view.to_string(),
+            format!("{} -> {}", type_name::<Cat>(), type_name::<dyn Feline>())
+        );
+
+        let registry = CastMutRegistry::new();
+
+        assert!(registry.records().any(|view| {
+            view.source_name() == type_name::<Rat>()
+                && view.target_name() == type_name::<dyn Rodent>()
+        }));
+    }
+
+    #[test]
+    fn test_registry_introspection() {
+        let mut implementors = registry::implementors_of::<dyn Mammal>()
+            .map(|view| view.source_id())
+            .collect::<Vec<_>>();
+
+        implementors.sort();
+
+        let mut expected = vec![
+            TypeId::of::<Cat>(),
+            TypeId::of::<Dog>(),
+            TypeId::of::<Rat>(),
+            TypeId::of::<Mouse>(),
+            TypeId::of::<Cage<u32>>(),
+        ];
+
+        expected.sort();
+
+        assert_eq!(implementors, expected);
+
+        let rat: Box<dyn Animal> = Box::new(Rat::new("Daisy"));
+        let mut targets = registry::targets_of(&*rat)
+            .map(|view| view.target_name())
+            .collect::<Vec<_>>();
+
+        targets.sort_unstable();
+
+        let mut expected = vec![
+            type_name::<dyn Animal>(),
+            type_name::<dyn Animal + Send>(),
+            type_name::<dyn Animal + Sync>(),
+            type_name::<dyn Animal + Send + Sync>(),
+            type_name::<dyn Mammal>(),
+            type_name::<dyn Mammal + Send>(),
+            type_name::<dyn Mammal + Sync>(),
+            type_name::<dyn Mammal + Send + Sync>(),
+            type_name::<dyn Rodent>(),
+            type_name::<dyn Rodent + Send>(),
+            type_name::<dyn Rodent + Sync>(),
+            type_name::<dyn Rodent + Send + Sync>(),
+        ];
+
+        expected.sort_unstable();
+
+        assert_eq!(targets, expected);
+
+        assert!(registry::can_cast::<Cat, dyn Feline>());
+        assert!(!registry::can_cast::<Cat, dyn Canine>());
+        assert!(CastMutRegistry::new().can_cast::<Dog, dyn Canine>());
+        assert!(!CastMutRegistry::new().can_cast::<Dog, dyn Rodent>());
+        assert_eq!(
+            CastRefRegistry::new()
+                .implementors_of::<dyn Feline>()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_registry_init() {
+        let stats = registry::init_parallel();
+
+        assert!(registry::is_initialized());
+        assert!(stats.ref_records() > 0);
+        assert!(stats.mut_records() > 0);
+        assert!(stats.total_records() >= stats.ref_records() + stats.mut_records());
+
+        assert!(stats.to_string().contains("cast records"));
+    }
+
+    #[test]
+    fn test_registry_init_again() {
+        registry::init();
+
+        let stats = registry::init();
+
+        assert!(!stats.performed_init());
+        assert!(registry::is_initialized());
+        assert!(stats.ref_records() > 0);
+    }
+
+    #[test]
+    fn test_registry_exports() {
+        let exports = registry::exports();
+
+        assert!(exports.ref_records() > 0);
+        assert!(exports.mut_records() > 0);
+        assert!(exports
+            .records()
+            .any(|view| view.target_name() == type_name::<dyn Feline>()));
+        // `Ferret` is neither `Send` nor `Sync`, so only its plain ref and mut records exist.
+        assert_eq!(
+            exports
+                .records()
+                .filter(|view| view.source_id() == TypeId::of::<Ferret>())
+                .count(),
+            2
+        );
+
+        // The exports are this artifact's own, which stay loaded for the whole run.
+        let import = unsafe { registry::import(exports) };
+
+        assert_eq!(import.ref_records(), 0);
+        assert_eq!(import.mut_records(), 0);
+    }
+
+    #[test]
+    fn test_registry_runtime_registration() {
+        let mut hamster: Box<dyn Mammal> = Box::new(Hamster::new("Hammy"));
+
+        assert!(!registry::can_cast::<Hamster, dyn Animal>());
+        assert!(hamster.cast_ref::<dyn Animal>().is_none());
+        assert!(hamster.cast_mut::<dyn Animal>().is_none());
+
+        registry::register::<Hamster, dyn Animal>();
+
+        assert!(registry::can_cast::<Hamster, dyn Animal>());
+        assert_eq!(hamster.cast_ref::<dyn Animal>().unwrap().name(), "Hammy");
+        assert!(hamster.cast_mut::<dyn Animal>().is_some());
+
+        let hamster = cast_box::<dyn Animal, _>(hamster).ok().unwrap();
+
+        assert_eq!(hamster.name(), "Hammy");
+
+        let hamster: Rc<dyn Mammal> = Rc::new(Hamster::new("Hammy"));
+        let hamster = cast_rc::<dyn Animal, _>(hamster).ok().unwrap();
+
+        assert_eq!(hamster.name(), "Hammy");
+
+        let registry = CastRefRegistry::default();
+
+        assert!(!registry.can_cast::<Cat, dyn Feline>());
+
+        registry.register(CastRefRecord::new::<Cat, dyn Feline>(|item| {
+            let item: &Cat = item.downcast_ref()?;
+
+            Some(item)
+        }));
+
+        let cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+
+        assert!(registry.can_cast::<Cat, dyn Feline>());
+        assert!(registry.cast_from_ref::<_, dyn Feline>(&*cat).is_some());
+        assert!(registry.cast_from_ref::<_, dyn Mammal>(&*cat).is_none());
+
+        let boxes = CastBoxRegistry::default();
+
+        boxes.register(CastBoxRecord::new::<Cat, dyn Feline>(|_| None));
+
+        let cat = boxes.cast_from_box::<_, dyn Feline>(cat).err().unwrap();
+
+        assert_eq!(cat.name(), "Felix");
+
+        let boxes = CastBoxRegistry::default();
+
+        boxes.register(CastBoxRecord::new::<Cat, dyn Feline>(|_| {
+            Some(Box::leak(Box::new(Cat::new("Tom"))))
+        }));
+
+        let cat = boxes.cast_from_box::<_, dyn Feline>(cat).err().unwrap();
+
+        assert_eq!(cat.name(), "Felix");
+
+        let rcs = CastRcRegistry::default();
+
+        rcs.register(CastRcRecord::new::<Cat, dyn Feline>(Err));
+
+        let cat: Rc<dyn Animal> = Rc::new(Cat::new("Felix"));
+        let cat = rcs.cast_from_rc::<_, dyn Feline>(cat).err().unwrap();
+
+        assert_eq!(Rc::strong_count(&cat), 1);
+        assert_eq!(cat.name(), "Felix");
+
+        let arcs = CastArcRegistry::default();
+
+        arcs.register(CastArcRecord::new::<Cat, dyn Feline + Send + Sync>(Err));
+
+        let cat: Arc<dyn Animal + Send + Sync> = Arc::new(Cat::new("Felix"));
+        let cat = arcs
+            .cast_from_arc::<_, dyn Feline + Send + Sync>(cat)
+            .err()
+            .unwrap();
+
+        assert_eq!(Arc::strong_count(&cat), 1);
+        assert_eq!(cat.name(), "Felix");
+    }
+
+    #[test]
+    fn test_registry_scoped() {
+        let mut cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+
+        let refs =
+            CastRefRegistry::new().filter(|view| view.target_id() != TypeId::of::<dyn Feline>());
+        let muts =
+            CastMutRegistry::new().filter(|view| view.target_id() != TypeId::of::<dyn Feline>());
+
+        registry::with_ref_registry(&refs, || {
+            assert!(cat.cast_ref::<dyn Feline>().is_none());
+            assert!(cat.cast_ref::<dyn Mammal>().is_some());
+            assert!(!registry::can_cast::<Cat, dyn Feline>());
+
+            let err = cat.try_cast_ref::<dyn Feline>().err().unwrap();
+
+            assert_eq!(err.kind(), CastErrorKind::Unregistered);
+        });
+
+        registry::with_mut_registry(&muts, || {
+            assert!(cat.cast_mut::<dyn Feline>().is_none());
+            assert!(cat.cast_mut::<dyn Mammal>().is_some());
+        });
+
+        assert!(cat.cast_ref::<dyn Feline>().is_some());
+        assert!(cat.cast_mut::<dyn Feline>().is_some());
+        assert!(registry::can_cast::<Cat, dyn Feline>());
+
+        let empty = CastRefRegistry::default();
+
+        registry::with_ref_registry(&empty, || {
+            assert!(cat.cast_ref::<dyn Mammal>().is_none());
+
+            registry::with_ref_registry(&refs, || {
+                assert!(cat.cast_ref::<dyn Mammal>().is_some());
+            });
+
+            assert!(cat.cast_ref::<dyn Mammal>().is_none());
+        });
+    }
+
+    #[test]
+    fn test_registry_scoped_owned() {
+        let boxes =
+            CastBoxRegistry::new().filter(|view| view.target_id() != TypeId::of::<dyn Feline>());
+        let rcs =
+            CastRcRegistry::new().filter(|view| view.target_id() != TypeId::of::<dyn Feline>());
+        let arcs = CastArcRegistry::new();
+        let arcs = arcs.filter(|view| view.target_id() != TypeId::of::<dyn Feline + Send + Sync>());
+
+        registry::with_box_registry(&boxes, || {
+            let cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+            let cat = registry::cast_from_box::<_, dyn Feline>(cat).err().unwrap();
+
+            assert!(registry::cast_from_box::<_, dyn Mammal>(cat).is_ok());
+        });
+
+        registry::with_rc_registry(&rcs, || {
+            let cat: Rc<dyn Animal> = Rc::new(Cat::new("Felix"));
+            let cat = registry::cast_from_rc::<_, dyn Feline>(cat).err().unwrap();
+
+            assert!(registry::cast_from_rc::<_, dyn Mammal>(cat).is_ok());
+        });
+
+        registry::with_arc_registry(&arcs, || {
+            let cat: Arc<dyn Animal + Send + Sync> = Arc::new(Cat::new("Felix"));
+            let cat = registry::cast_from_arc::<_, dyn Feline + Send + Sync>(cat);
+
+            assert!(cat.is_err());
+        });
+
+        let cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+
+        assert!(registry::cast_from_box::<_, dyn Feline>(cat).is_ok());
+
+        let cat: Arc<dyn Animal + Send + Sync> = Arc::new(Cat::new("Felix"));
+
+        assert!(registry::cast_from_arc::<_, dyn Feline + Send + Sync>(cat).is_ok());
+    }
+
+    #[cfg(not(any(feature = "strict-registry", feature = "static-registry")))]
+    mod duplicate {
+        use super::{Cat, Feline};
+
+        crate::register_cast_ref!(struct Cat: Feline);
+        crate::register_cast_box!(struct Cat: Feline);
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "strict-registry", feature = "static-registry")))]
+    fn test_registry_validate() {
+        let refs = CastRefRegistry::new();
+        let muts = CastMutRegistry::new();
+        let report = registry::with_ref_registry(&refs, || {
+            registry::with_mut_registry(&muts, registry::validate)
+        });
+        let dups = report
+            .duplicates()
+            .filter(|dup| dup.view().source_id() == TypeId::of::<Cat>())
+            .collect::<Vec<_>>();
+
+        assert!(!report.is_valid());
+        assert!(report.parity().is_valid());
+        // Ref and box each duplicate the base record and its `Send`/`Sync` variants.
+        assert_eq!(dups.len(), 8);
+        assert!(dups.iter().all(|dup| dup.count() == 2));
+        assert!(dups
+            .iter()
+            .all(|dup| dup.view().target_name().contains("Feline")));
+        assert_eq!(
+            dups.iter()
+                .filter(|dup| dup.kind() == registry::CastRecordKind::Box)
+                .count(),
+            4
+        );
+        assert!(report.to_string().contains("registered 2 times"));
+
+        let muts = CastMutRegistry::default();
+        let report = registry::with_ref_registry(&refs, || {
+            registry::with_mut_registry(&muts, registry::validate)
+        });
+
+        assert!(report.parity().ref_only().count() > 0);
+        assert!(report
+            .to_string()
+            .contains("registered for ref but not mut"));
+
+        let cat: Box<dyn Animal> = Box::new(Cat::new("Felix"));
+
+        assert!(cat.cast_ref::<dyn Feline>().is_some());
+    }
+
+    #[test]
+    fn test_registry_parity() {
+        let refs = CastRefRegistry::new();
+        let muts = CastMutRegistry::new();
+
+        assert!(CastParityReport::new(&refs, &muts).is_valid());
+
+        let muts = muts.filter(|view| view.source_id() != TypeId::of::<Cat>());
+        let refs = refs.filter(|view| view.target_id() != TypeId::of::<dyn Rodent>());
+
+        registry::with_ref_registry(&refs, || {
+            registry::with_mut_registry(&muts, || {
+                let report = registry::parity();
+
+                assert!(!report.is_valid());
+                assert!(report
+                    .ref_only()
+                    .all(|view| view.source_id() == TypeId::of::<Cat>()));
+                assert!(report
+                    .mut_only()
+                    .all(|view| view.target_id() == TypeId::of::<dyn Rodent>()));
+                // Three targets for `Cat`, each with a base and three `Send`/`Sync` variants.
+                assert_eq!(report.ref_only().count(), 12);
+                assert_eq!(report.mut_only().count(), 2);
+            });
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "registered for ref but not mut")]
+    fn test_registry_assert_parity() {
+        let refs = CastRefRegistry::new();
+        let muts = CastMutRegistry::default();
+
+        registry::with_ref_registry(&refs, || {
+            registry::with_mut_registry(&muts, registry::assert_parity);
+        });
+    }
+
+    #[test]
+    fn test_cast_trait_object_undeclared_pair() {
+        let mut cat: Box<dyn Feline> = Box::new(Cat::new("Felix"));
+
+        assert_eq!(cat.cast_ref::<dyn Animal>().unwrap().name(), "Felix");
+        assert_eq!(cat.cast_mut::<dyn Mammal>().unwrap().legs(), &4);
+        assert_eq!(cat.cast_ref::<Cat>().unwrap().name(), "Felix");
+
+        let cat: Rc<dyn Mammal> = Rc::new(Cat::new("Felix"));
+
+        assert_eq!(cat.cast_ref::<dyn Feline>().unwrap().eyes(), &2);
+
+        let mut dog: Box<dyn Canine> = Box::new(Dog::new("Rover"));
+
+        assert_eq!(dog.cast_ref::<dyn Animal>().unwrap().name(), "Rover");
+        assert!(dog.cast_ref::<dyn Feline>().is_none());
+        assert_eq!(
+            dog.try_cast_mut::<dyn Feline>().err().unwrap().kind(),
+            CastErrorKind::Unregistered
+        );
+    }
+
+    #[test]
+    fn test_cast_trait_object_supertrait_closure() {
+        let collie = Collie {
+            owner: "Sam".into(),
+            flock: 40,
+        };
+        let mut collie: Box<dyn Herder> = Box::new(collie);
+
+        assert_eq!(collie.cast_ref::<dyn Pet>().unwrap().owner(), "Sam");
+        assert_eq!(collie.cast_mut::<dyn Working>().unwrap().job(), "herding");
+
+        let collie = cast_box::<dyn Pet, _>(collie).ok().unwrap();
+
+        assert_eq!(collie.cast_ref::<dyn Herder>().unwrap().flock(), &40);
+
+        let collie: Rc<dyn Pet> = Rc::from(collie);
+        let collie = cast_rc::<dyn Herder, _>(collie).ok().unwrap();
+
+        assert_eq!(collie.flock(), &40);
+    }
+
+    #[test]
+    fn test_cast_trait_object_default_closure() {
+        let collie = Collie {
+            owner: "Sam".into(),
+            flock: 40,
+        };
+        let collie: Box<dyn Shepherd> = Box::new(collie);
+
+        assert_eq!(collie.cast_ref::<dyn Herder>().unwrap().flock(), &40);
+        assert_eq!(collie.cast_ref::<dyn Working>().unwrap().job(), "herding");
+        assert_eq!(collie.cast_ref::<dyn Pet>().unwrap().owner(), "Sam");
+        assert_eq!(collie.cast_ref::<dyn Tagged>().unwrap().tag(), "collie");
+
+        let collie = cast_box::<dyn Pet, _>(collie).ok().unwrap();
+
+        assert_eq!(
+            collie.cast_ref::<dyn Shepherd>().unwrap().whistle(),
+            "come by"
+        );
+    }
+
+    #[test]
+    fn test_cast_struct_supertrait_closure() {
+        let kelpie = Kelpie {
+            owner: "Sam".into(),
+        };
+
+        assert!(kelpie.cast_ref::<dyn Pet>().is_some());
+        assert!(kelpie.cast_ref::<dyn Working>().is_some());
+        assert!(kelpie.cast_ref::<dyn Herder>().is_some());
+
+        let mut kelpie: Box<dyn Pet> = Box::new(kelpie);
+
+        assert_eq!(kelpie.cast_ref::<Kelpie>().unwrap().owner, "Sam");
+        assert_eq!(kelpie.cast_mut::<dyn Working>().unwrap().job(), "droving");
+        assert_eq!(kelpie.cast_ref::<dyn Herder>().unwrap().flock(), &120);
+
+        let kelpie = cast_box::<dyn Working, _>(kelpie).ok().unwrap();
+        let kelpie = cast_box::<Kelpie, _>(kelpie).ok().unwrap();
+
+        assert_eq!(kelpie.owner(), "Sam");
+    }
+
+    #[test]
+    fn test_cast_generic_struct() {
+        let cage = Cage::new("Polly", 7u8);
+
+        assert_eq!(cage.cast_ref::<dyn Animal>().unwrap().name(), "Polly");
+        assert!(!registry::can_cast::<Cage<u8>, dyn Animal>());
+        assert!(registry::can_cast::<Cage<u32>, dyn Animal>());
+        assert!(registry::can_cast::<Cage<String>, dyn Animal>());
+
+        let mut cage: Box<dyn Animal> = Box::new(Cage::new("Polly", 7u32));
+
+        assert_eq!(cage.cast_ref::<Cage<u32>>().unwrap().tag, 7);
+        assert!(cage.cast_ref::<Cage<String>>().is_none());
+        assert_eq!(cage.cast_mut::<dyn Mammal>().unwrap().legs(), &4);
+
+        let cage: Box<dyn Animal> = Box::new(Cage::new("Polly", String::from("seven")));
+
+        assert_eq!(cage.cast_ref::<Cage<String>>().unwrap().tag, "seven");
+        assert!(cage.cast_ref::<dyn Mammal>().is_none());
+
+        let cage: Arc<dyn Animal + Send + Sync> = Arc::new(Cage::new("Polly", 7u32));
+        let cage = cast_arc::<dyn Mammal + Send + Sync, _>(cage).ok().unwrap();
+
+        assert_eq!(cage.legs(), &4);
+    }
+
+    #[test]
+    fn test_cast_generic_trait_object() {
+        let button: Box<dyn Handler<Click>> = Box::new(Button { clicks: 3 });
+
+        assert_eq!(button.handle(&Click), 3);
+        assert_eq!(
+            button
+                .cast_ref::<dyn Listener<Click>>()
+                .unwrap()
+                .listen(&Click),
+            "click"
+        );
+        assert!(registry::can_cast::<Button, dyn Handler<Press>>());
+        assert!(registry::can_cast::<Button, dyn Listener<Press>>());
+
+        let button = cast_box::<dyn Listener<Click>, _>(button).ok().unwrap();
+        let button = cast_box::<dyn Handler<Click>, _>(button).ok().unwrap();
+        let button = registry::cast_from_box::<_, dyn Handler<Press>>(button)
+            .ok()
+            .unwrap();
+
+        assert_eq!(button.handle(&Press), 6);
+        assert_eq!(
+            button
+                .cast_ref::<dyn Listener<Press>>()
+                .unwrap()
+                .listen(&Press),
+            "press"
+        );
+
+        let mut burrow: Box<dyn Animal> = Box::new(Burrow {
+            name: String::from("Warren"),
+            pups: 2,
+        });
+
+        assert!(burrow.cast_ref::<dyn Litter<Pup = u16>>().is_none());
+
+        let litter = burrow.cast_mut::<dyn Litter<Pup = u8>>().unwrap();
+
+        assert_eq!(litter.next_pup(), Some(1));
+        assert_eq!(cast_ref::<dyn Animal, _>(litter).unwrap().name(), "Warren");
+    }
+
+    #[test]
+    fn test_cast_auto_trait_object() {
+        let cat = Cat::new("Felix");
+
+        assert!(cat.cast_ref::<dyn Feline + Send + Sync>().is_some());
+
+        let cat: Box<dyn Animal + Send + Sync> = Box::new(cat);
+        let cat = cast_box::<dyn Feline + Send + Sync, _>(cat).ok().unwrap();
+
+        assert_eq!(cat.eyes(), &2);
+
+        let mut dog: Box<dyn Animal + Send> = Box::new(Dog::new("Rover"));
+
+        assert!(dog.cast_ref::<dyn Canine + Send>().is_some());
+        assert!(dog.cast_mut::<dyn Feline + Send>().is_none());
+
+        let rat: Rc<dyn Animal + Sync> = Rc::new(Rat::new("Daisy"));
+        let rat = cast_rc::<dyn Rodent + Sync, _>(rat).ok().unwrap();
+
+        assert_eq!(rat.tail(), &true);
+
+        let ferret: Box<dyn Animal> = Box::new(Ferret {
+            name: Rc::from("Bandit"),
+        });
+
+        assert_eq!(ferret.cast_ref::<Ferret>().unwrap().name(), "Bandit");
+        assert!(registry::can_cast::<Ferret, dyn Animal>());
+        assert!(!registry::can_cast::<Ferret, dyn Animal + Send>());
+        assert!(!registry::can_cast::<Ferret, dyn Animal + Sync>());
+        assert!(registry::can_cast::<Cat, dyn Feline + Send + Sync>());
+    }
+
+    #[test]
+    fn test_cast_from_any() {
+        let cat = Cat::new("Felix");
+        let any: &dyn Any = &cat;
+
+        assert_eq!(
+            registry::cast_any_ref::<dyn Feline>(any).unwrap().eyes(),
+            &2
+        );
+
+        let err = registry::try_cast_any_ref::<dyn Canine>(any).err().unwrap();
+
+        assert_eq!(err.kind(), CastErrorKind::Unregistered);
+        assert_eq!(err.source_name(), type_name::<dyn Any>());
+
+        let mut dog = Dog::new("Rover");
+        let any: &mut dyn Any = &mut dog;
+
+        assert_eq!(
+            registry::cast_any_mut::<dyn Canine>(any).unwrap().ears(),
+            &2
+        );
+
+        let payload: Box<dyn Any + Send> = Box::new(Rat::new("Daisy"));
+        let rat = registry::cast_any_box::<dyn Rodent>(payload).ok().unwrap();
+
+        assert_eq!(rat.name(), "Daisy");
+
+        let payload = registry::cast_any_box::<dyn Animal>(Box::new(5u8))
+            .err()
+            .unwrap();
+
+        assert_eq!(payload.downcast_ref::<u8>(), Some(&5));
+    }
+
+    #[test]
+    fn test_cast_interface() {
+        let id = <dyn Rider as CastInterface>::ID;
+
+        assert_eq!(
+            <dyn Grazer as CastInterface>::ID,
+            InterfaceId::new("brace.test.Grazer/1")
+        );
+        assert_eq!(id, InterfaceId::new(concat!(module_path!(), "::Rider")));
+        assert_eq!(
+            registry::interface(id).unwrap().target_name(),
+            type_name::<dyn Rider>()
+        );
+
+        let mut horse: Box<dyn Grazer> = Box::new(Horse {
+            pasture: "Meadow".into(),
+            saddled: false,
+        });
+
+        *registry::cast_interface_mut::<_, dyn Rider>(&mut *horse)
+            .unwrap()
+            .saddled() = true;
+
+        let rider = registry::cast_interface_ref::<_, dyn Rider>(&*horse).unwrap();
+
+        assert!(cast_ref::<Horse, _>(rider).unwrap().saddled);
+        assert_eq!(
+            registry::cast_interface_ref::<_, dyn Grazer>(rider)
+                .unwrap()
+                .pasture(),
+            "Meadow"
+        );
+        assert!(registry::cast_interface_ref::<_, dyn Grazer>(&Cat::new("Felix")).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "ffi")]
+    fn test_ffi_query() {
+        use std::ptr;
+
+        use crate::ffi::{self, CastHandle, CastHeader, CastObject};
+
+        #[repr(C)]
+        struct GrazerVtbl {
+            pasture_len: unsafe extern "C" fn(*const CastHandle) -> usize,
+        }
+
+        unsafe extern "C" fn pasture_len(handle: *const CastHandle) -> usize {
+            CastObject::from_handle_ref(handle)
+                .and_then(|object| object.query::<dyn Grazer>())
+                .map_or(0, |grazer| grazer.pasture().len())
+        }
+
+        static GRAZER: GrazerVtbl = GrazerVtbl { pasture_len };
+
+        let horse = CastObject::new(Horse {
+            pasture: "Meadow".into(),
+            saddled: false,
+        });
+        let id = <dyn Grazer as CastInterface>::ID;
+        let handle = horse.into_handle();
+
+        unsafe {
+            assert_eq!(ffi::brace_cast_supports(handle, id.to_raw()), 1);
+            assert_eq!(
+                ffi::brace_cast_supports(handle, InterfaceId::new("none").to_raw()),
+                0
+            );
+            assert_eq!(ffi::brace_cast_supports(ptr::null(), id.to_raw()), 0);
+            assert!(ffi::brace_cast_query(handle, id.to_raw()).is_null());
+
+            ffi::register_vtable::<dyn Grazer, _>(&GRAZER);
+
+            let vtable = ffi::brace_cast_query(handle, id.to_raw()).cast::<GrazerVtbl>();
+
+            assert!(ptr::eq(vtable, &GRAZER));
+            assert_eq!(((*vtable).pasture_len)(handle), 6);
+            assert!(ffi::brace_cast_query(handle, <dyn Rider>::ID.to_raw()).is_null());
+            assert!(ffi::brace_cast_query(ptr::null(), id.to_raw()).is_null());
+
+            ffi::brace_cast_release(handle);
+        }
+
+        let header = CastHeader::new()
+            .guard("HORSE_H")
+            .interface::<dyn Grazer>("GRAZER")
+            .registered()
+            .vtable(
+                "GrazerVtbl",
+                &["size_t (*pasture_len)(const BraceCastObject *object)"],
+            )
+            .to_string();
+
+        assert!(header.starts_with("#ifndef HORSE_H\n#define HORSE_H\n"));
+        assert!(header.contains(&format!(
+            "#define BRACE_CAST_IID_GRAZER UINT64_C(0x{})\n",
+            id
+        )));
+        assert!(!header.contains("BRACE_CAST_IID_BRACE_CAST_TESTS_GRAZER"));
+        assert!(header.contains("#define BRACE_CAST_IID_BRACE_CAST_TESTS_RIDER UINT64_C(0x"));
+        assert!(header.contains("#define BRACE_CAST_IID_BRACE_CAST_TESTS_PADDOCK_RIDER UINT64_C"));
+
+        assert!(header.contains(
+            "typedef struct GrazerVtbl {\n    \
+             size_t (*pasture_len)(const BraceCastObject *object);\n} GrazerVtbl;\n"
+        ));
+        assert!(header.contains("int brace_cast_supports("));
+        assert!(header.contains("const void *brace_cast_query("));
+
+        let rider = <dyn Rider>::ID;
+        let header = CastHeader::new()
+            .interface_id(
+                "BRACE_CAST_TESTS_RIDER",
+                InterfaceId::new("brace.test.Stirrup/1"),
+            )
+            .registered()
+            .to_string();
+
+        assert!(header.contains(&format!(
+            "#define BRACE_CAST_IID_BRACE_CAST_TESTS_RIDER_{:016X} UINT64_C(0x{})\n",
+            rider.to_raw(),
+            rider
+        )));
+    }
+
+    #[cfg(feature = "static-registry")]
+    mod static_table {
+        use std::rc::Rc;
+
+        use crate::{cast, cast_box, cast_rc, registry, Cast};
+
+        #[cast]
+        pub(super) trait Animal: Cast {
+            fn name(&self) -> &str;
+        }
+
+        #[cast(id = "brace.test.Mammal/1")]
+        pub(super) trait Mammal: Animal {}
+
+        #[cast]
+        pub(super) trait Canine: Animal {}
+
+        pub(super) struct Cat(&'static str);
+
+        #[cast]
+        impl Animal for Cat {
+            fn name(&self) -> &str {
+                self.0
+            }
+        }
+
+        #[cast]
+        impl Mammal for Cat {}
+
+        pub(super) struct Dog(&'static str);
+
+        #[cast]
+        impl Animal for Dog {
+            fn name(&self) -> &str {
+                self.0
+            }
+        }
+
+        #[cast]
+        impl Mammal for Dog {}
+
+        #[cast]
+        impl Canine for Dog {}
+
+        struct Fox(&'static str);
+
+        #[cast]
+        impl Animal for Fox {
+            fn name(&self) -> &str {
+                self.0
+            }
+        }
+
+        #[cast]
+        impl Canine for Fox {}
+
+        #[test]
+        fn test_static_table() {
+            let mut cat: Box<dyn Animal> = Box::new(Cat("Felix"));
+            let dog: Box<dyn Animal> = Box::new(Dog("Rover"));
+
+            assert_eq!(cat.cast_ref::<dyn Mammal>().unwrap().name(), "Felix");
+            assert!(cat.cast_mut::<dyn Mammal>().is_some());
+            assert!(cat.cast_ref::<dyn Canine>().is_none());
+            assert_eq!(dog.cast_ref::<dyn Canine>().unwrap().name(), "Rover");
+            assert!(registry::cast_interface_ref::<_, dyn Mammal>(&*dog).is_some());
+
+            let dog = cast_box::<dyn Canine, _>(dog).ok().unwrap();
+
+            assert_eq!(dog.name(), "Rover");
+
+            let dog: Rc<dyn Animal> = Rc::new(Dog("Rover"));
+            let dog = cast_rc::<dyn Mammal, _>(dog).ok().unwrap();
+
+            assert_eq!(dog.name(), "Rover");
+        }
+
+        #[test]
+        fn test_static_table_excludes_unlisted() {
+            let fox: Box<dyn Animal> = Box::new(Fox("Todd"));
+
+            assert!(!registry::can_cast::<Fox, dyn Canine>());
+            assert!(fox.cast_ref::<dyn Canine>().is_none());
+            assert!(
+                registry::implementors_of::<dyn Canine>().all(|view| view.source_name() != "Fox")
+            );
+
+            registry::register_ref::<Fox, dyn Canine>();
+
+            assert_eq!(fox.cast_ref::<dyn Canine>().unwrap().name(), "Todd");
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod tests_no_std {
+    #[cfg(feature = "alloc")]
+    use alloc::boxed::Box;
+    #[cfg(feature = "alloc")]
+    use alloc::rc::Rc;
+    #[cfg(feature = "alloc")]
+    use core::any::Any;
+
+    #[cfg(feature = "alloc")]
+    use crate::registry;
+    #[cfg(feature = "alloc")]
+    use crate::{cast_box, cast_rc, impl_cast_as_ref};
+    use crate::{cast_mut, cast_ref, impl_cast_as, Cast};
+
+    trait Shape: Cast {
+        fn sides(&self) -> usize;
+    }
+
+    trait Polygon: Shape {
+        fn area(&self) -> usize;
+    }
+
+    struct Square(usize);
+
+    impl Shape for Square {
+        fn sides(&self) -> usize {
+            4
+        }
+    }
+
+    impl Polygon for Square {
+        fn area(&self) -> usize {
+            self.0 * self.0
+        }
+    }
+
+    impl_cast_as!(struct Square: Shape, Polygon);
+
+    #[cfg(feature = "alloc")]
+    impl_cast_as!(trait Shape: Polygon);
+
+    #[cfg(feature = "static-registry")]
+    crate::cast_table! {
+        Square: Shape, Polygon;
+    }
+
+    #[test]
+    fn test_cast_struct_as_trait_object() {
+        let mut square = Square(3);
+
+        assert_eq!(cast_ref::<dyn Shape, _>(&square).unwrap().sides(), 4);
+        assert_eq!(cast_mut::<dyn Polygon, _>(&mut square).unwrap().area(), 9);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_cast_trait_object_registry() {
+        let mut square: Box<dyn Shape> = Box::new(Square(3));
+
+        assert_eq!(square.cast_ref::<dyn Polygon>().unwrap().area(), 9);
+        assert_eq!(square.cast_mut::<dyn Polygon>().unwrap().area(), 9);
+        assert!(registry::can_cast::<Square, dyn Polygon>());
+
+        let square = cast_box::<dyn Polygon, _>(square).ok().unwrap();
+
+        assert_eq!(square.sides(), 4);
+
+        let square: Rc<dyn Shape> = Rc::new(Square(2));
+        let square = cast_rc::<dyn Polygon, _>(square).ok().unwrap();
+
+        assert_eq!(square.area(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_register_runtime() {
+        struct Circle;
+
+        impl Shape for Circle {
+            fn sides(&self) -> usize {
+                0
+            }
+        }
+
+        impl_cast_as_ref!(impl [] struct Circle: Shape);
+
+        let circle: Box<dyn Any> = Box::new(Circle);
+
+        assert!(registry::cast_any_ref::<dyn Shape>(&*circle).is_none());
+
+        registry::register_ref::<Circle, dyn Shape>();
+
+        assert_eq!(
+            registry::cast_any_ref::<dyn Shape>(&*circle)
+                .unwrap()
+                .sides(),
+            0
+        );
+    }
 }
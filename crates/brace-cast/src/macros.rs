@@ -1,38 +1,693 @@
-pub use std::any::Any;
-pub use std::option::Option;
+pub use core::any::Any;
+pub use core::marker::{Send, Sync};
+pub use core::option::Option;
+pub use core::result::Result;
 
+#[cfg(feature = "alloc")]
+pub use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+pub use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+pub use alloc::sync::Arc;
+
+pub use brace_cast_macros::cast_closure;
+
+use core::any::type_name;
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+
+#[cfg(feature = "alloc")]
+use crate::registry::{
+    CastArcHandler, CastBoxHandler, CastMutHandler, CastRcHandler, CastRefHandler,
+};
+use crate::{CastAsAny, CastError, CastErrorKind};
+#[cfg(feature = "alloc")]
+use crate::{CastAsArc, CastAsMut, CastAsRc, CastAsRef};
+
+pub fn try_downcast_ref<S, T>(from: &S) -> Result<&T, CastError>
+where
+    S: CastAsAny + ?Sized + 'static,
+    T: 'static,
+{
+    let source = from.cast_type_name();
+
+    from.cast_as_any_ref()
+        .downcast_ref()
+        .ok_or_else(|| CastError::new(source, type_name::<T>(), CastErrorKind::Mismatch))
+}
+
+pub fn try_downcast_mut<S, T>(from: &mut S) -> Result<&mut T, CastError>
+where
+    S: CastAsAny + ?Sized + 'static,
+    T: 'static,
+{
+    let source = from.cast_type_name();
+
+    from.cast_as_any_mut()
+        .downcast_mut()
+        .ok_or_else(|| CastError::new(source, type_name::<T>(), CastErrorKind::Mismatch))
+}
+
+#[cfg(feature = "alloc")]
+pub fn downcast_box<S, T>(from: Box<S>) -> Result<Box<T>, Box<S>>
+where
+    S: CastAsAny + ?Sized + 'static,
+    T: 'static,
+{
+    if !(*from).cast_as_any_ref().is::<T>() {
+        return Err(from);
+    }
+
+    // The concrete type behind `from` was just checked to be `T`.
+    Ok(unsafe { Box::from_raw(Box::into_raw(from).cast::<T>()) })
+}
+
+#[cfg(feature = "alloc")]
+pub fn downcast_rc<S, T>(from: Rc<S>) -> Result<Rc<T>, Rc<S>>
+where
+    S: CastAsAny + ?Sized + 'static,
+    T: 'static,
+{
+    if !(*from).cast_as_any_ref().is::<T>() {
+        return Err(from);
+    }
+
+    // The concrete type behind `from` was just checked to be `T`.
+    Ok(unsafe { Rc::from_raw(Rc::into_raw(from).cast::<T>()) })
+}
+
+#[cfg(feature = "alloc")]
+pub fn downcast_arc<S, T>(from: Arc<S>) -> Result<Arc<T>, Arc<S>>
+where
+    S: CastAsAny + Send + Sync + ?Sized + 'static,
+    T: Send + Sync + 'static,
+{
+    if !(*from).cast_as_any_ref().is::<T>() {
+        return Err(from);
+    }
+
+    // The concrete type behind `from` was just checked to be `T`.
+    Ok(unsafe { Arc::from_raw(Arc::into_raw(from).cast::<T>()) })
+}
+
+#[cfg(feature = "alloc")]
+pub struct CastRefProbe<S, T: ?Sized>(PhantomData<fn(S) -> Box<T>>);
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> Default for CastRefProbe<S, T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastRefSupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastRefHandler<T>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T> CastRefSupported<T> for CastRefProbe<S, T>
+where
+    S: CastAsRef<T> + 'static,
+    T: ?Sized + 'static,
+{
+    fn handler(&self) -> Option<CastRefHandler<T>> {
+        Some(|item| {
+            let item: &S = item.downcast_ref()?;
+
+            item.cast_as_ref()
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastRefUnsupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastRefHandler<T>> {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> CastRefUnsupported<T> for &CastRefProbe<S, T> {}
+
+#[cfg(feature = "alloc")]
+pub struct CastMutProbe<S, T: ?Sized>(PhantomData<fn(S) -> Box<T>>);
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> Default for CastMutProbe<S, T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastMutSupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastMutHandler<T>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T> CastMutSupported<T> for CastMutProbe<S, T>
+where
+    S: CastAsMut<T> + 'static,
+    T: ?Sized + 'static,
+{
+    fn handler(&self) -> Option<CastMutHandler<T>> {
+        Some(|item| {
+            let item: &mut S = item.downcast_mut()?;
+
+            item.cast_as_mut()
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastMutUnsupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastMutHandler<T>> {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> CastMutUnsupported<T> for &CastMutProbe<S, T> {}
+
+#[cfg(feature = "alloc")]
+pub struct CastBoxProbe<S, T: ?Sized>(PhantomData<fn(S) -> Box<T>>);
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> Default for CastBoxProbe<S, T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastBoxSupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastBoxHandler<T>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T> CastBoxSupported<T> for CastBoxProbe<S, T>
+where
+    S: CastAsMut<T> + 'static,
+    T: ?Sized + 'static,
+{
+    fn handler(&self) -> Option<CastBoxHandler<T>> {
+        Some(|item| {
+            let item: &mut S = item.downcast_mut()?;
+
+            item.cast_as_mut()
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastBoxUnsupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastBoxHandler<T>> {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> CastBoxUnsupported<T> for &CastBoxProbe<S, T> {}
+
+#[cfg(feature = "alloc")]
+pub struct CastRcProbe<S, T: ?Sized>(PhantomData<fn(S) -> Box<T>>);
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> Default for CastRcProbe<S, T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastRcSupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastRcHandler<T>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T> CastRcSupported<T> for CastRcProbe<S, T>
+where
+    S: CastAsRc<T> + 'static,
+    T: ?Sized + 'static,
+{
+    fn handler(&self) -> Option<CastRcHandler<T>> {
+        Some(|item| {
+            let item: Rc<S> = item.downcast()?;
+
+            match item.cast_as_rc() {
+                Ok(item) => Ok(item),
+                Err(item) => Err(item),
+            }
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastRcUnsupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastRcHandler<T>> {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> CastRcUnsupported<T> for &CastRcProbe<S, T> {}
+
+#[cfg(feature = "alloc")]
+pub struct CastArcProbe<S, T: ?Sized>(PhantomData<fn(S) -> Box<T>>);
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> Default for CastArcProbe<S, T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastArcSupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastArcHandler<T>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T> CastArcSupported<T> for CastArcProbe<S, T>
+where
+    S: CastAsArc<T> + Send + Sync + 'static,
+    T: ?Sized + 'static,
+{
+    fn handler(&self) -> Option<CastArcHandler<T>> {
+        Some(|item| {
+            let item: Arc<S> = item.downcast()?;
+
+            match item.cast_as_arc() {
+                Ok(item) => Ok(item),
+                Err(item) => Err(item),
+            }
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub trait CastArcUnsupported<T: ?Sized> {
+    fn handler(&self) -> Option<CastArcHandler<T>> {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T: ?Sized> CastArcUnsupported<T> for &CastArcProbe<S, T> {}
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
 #[macro_export]
-macro_rules! register_cast_ref {
-    (struct $from:path : $as:path) => {
+macro_rules! __brace_cast_alloc {
+    ($($tt:tt)*) => {
+        $($tt)*
+    };
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __brace_cast_alloc {
+    ($($tt:tt)*) => {};
+}
+
+/// Continues a supertrait closure through a trait without a `#[cast]` descriptor, which
+/// contributes no supertraits of its own.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __brace_cast_leaf {
+    (@closure { $($state:tt)* }) => {
+        $crate::macros::cast_closure! {
+            $($state)*
+        }
+    };
+}
+
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __brace_cast_submit {
+    ($record:ident => $slice:ident, $value:expr) => {
         $crate::inventory::submit! {
             #![crate = $crate]
-            $crate::registry::CastRefRecord::new::<$from, dyn $as>(
-                |item| {
-                    let item: &$from = $crate::macros::Any::downcast_ref(item)?;
-                    let item: &dyn $as = item;
+            $value
+        }
+    };
+}
 
-                    $crate::macros::Option::Some(item)
-                }
-            )
+#[cfg(all(feature = "linkme", not(feature = "static-registry")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __brace_cast_submit {
+    ($record:ident => $slice:ident, $value:expr) => {
+        const _: () = {
+            #[$crate::linkme::distributed_slice($crate::registry::$slice)]
+            #[linkme(crate = $crate::linkme)]
+            static RECORD: fn() -> $crate::registry::CastRecordSet<$crate::registry::$record> =
+                || $value;
+        };
+    };
+}
+
+#[cfg(feature = "static-registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __brace_cast_submit {
+    ($record:ident => $slice:ident, $value:expr) => {};
+}
+
+/// Expands `$mac!($($pre)* [$($auto)*] ...)` once per trait object variant: every combination of
+/// `Send` and `Sync` for `all`, only `Send + Sync` for `shared`. `@list` collects the expansions
+/// into an array instead.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __brace_cast_auto {
+    (all $mac:ident!($($pre:tt)*) $($args:tt)*) => {
+        $crate::$mac!($($pre)* [] $($args)*);
+        $crate::$mac!($($pre)* [+ $crate::macros::Send] $($args)*);
+        $crate::$mac!($($pre)* [+ $crate::macros::Sync] $($args)*);
+        $crate::$mac!($($pre)* [+ $crate::macros::Send + $crate::macros::Sync] $($args)*);
+    };
+
+    (shared $mac:ident!($($pre:tt)*) $($args:tt)*) => {
+        $crate::$mac!($($pre)* [+ $crate::macros::Send + $crate::macros::Sync] $($args)*);
+    };
+
+    (@list all $mac:ident!($($pre:tt)*) $($args:tt)*) => {
+        [
+            $crate::$mac!($($pre)* [] $($args)*),
+            $crate::$mac!($($pre)* [+ $crate::macros::Send] $($args)*),
+            $crate::$mac!($($pre)* [+ $crate::macros::Sync] $($args)*),
+            $crate::$mac!($($pre)* [+ $crate::macros::Send + $crate::macros::Sync] $($args)*),
+        ]
+    };
+
+    (@list shared $mac:ident!($($pre:tt)*) $($args:tt)*) => {
+        [$crate::$mac!($($pre)* [+ $crate::macros::Send + $crate::macros::Sync] $($args)*)]
+    };
+}
+
+/// The input forms shared by the `impl_cast_as_*` and `impl_cast_from_*` macros, lowered to
+/// their `@auto` arms.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __brace_cast_forward {
+    ($mac:ident, $register:ident, $auto:ident; struct $from:path : $($as:path),+) => {
+        $(
+            $crate::$register!(struct $from : $as);
+            $crate::$mac!(impl [] struct $from : $as);
+        )+
+    };
+
+    ($mac:ident, $register:ident, $auto:ident; trait $from:path : $($as:path),+) => {
+        $crate::$mac!(impl [] trait $from : $($as),+);
+    };
+
+    ($mac:ident, $register:ident, $auto:ident; impl $gen:tt struct $from:ty : $($as:path),+) => {
+        $crate::$mac!(impl $gen struct $from : $($as),+ where []);
+    };
+
+    ($mac:ident, $register:ident, $auto:ident; impl $gen:tt trait $from:path : $($as:path),+) => {
+        $crate::$mac!(impl $gen trait $from : $($as),+ where []);
+    };
+
+    (
+        $mac:ident, $register:ident, $auto:ident;
+        impl $gen:tt struct $from:ty : $($as:path),+ where $pred:tt
+    ) => {
+        $(
+            $crate::__brace_cast_auto!($auto $mac!(@auto) impl $gen struct $from : $as where $pred);
+        )+
+    };
+
+    (
+        $mac:ident, $register:ident, $auto:ident;
+        impl $gen:tt trait $from:path : $($as:path),+ where $pred:tt
+    ) => {
+        $(
+            $crate::__brace_cast_auto!($auto $mac!(@auto) impl $gen trait $from : $as where $pred);
+        )+
+    };
+}
+
+#[macro_export]
+macro_rules! register_cast {
+    (struct $from:path : $as:path $(, $also:path)*) => {
+        $crate::register_cast_ref!(struct $from : $as);
+        $crate::register_cast_mut!(struct $from : $as);
+        $crate::__brace_cast_alloc! {
+            $crate::register_cast_box!(struct $from : $as);
+            $crate::register_cast_rc!(struct $from : $as);
+            $crate::register_cast_arc!(struct $from : $as);
         }
+        $(
+            $crate::register_cast!(struct $from : $also);
+        )*
+    };
+}
+
+#[macro_export]
+macro_rules! register_cast_ref {
+    (struct $from:path : $as:path) => {
+        $crate::__brace_cast_alloc! {
+            $crate::__brace_cast_submit! {
+                CastRefRecord => CAST_REF_RECORDS,
+                $crate::register_cast_ref!(@records struct $from : $as)
+            }
+        }
+    };
+
+    (@records struct $from:path : $as:path) => {
+        $crate::registry::CastRecordSet::new($crate::__brace_cast_auto!(
+            @list all register_cast_ref!(@record) struct $from : $as
+        ))
+    };
+
+    (@record [] struct $from:path : $as:path) => {
+        $crate::macros::Option::Some($crate::registry::CastRefRecord::new::<$from, dyn $as>(|item| {
+            let item: &$from = <dyn $crate::macros::Any>::downcast_ref(item)?;
+            let item: &dyn $as = item;
+
+            $crate::macros::Option::Some(item)
+        }))
+    };
+
+    (@record [$($auto:tt)*] struct $from:path : $as:path) => {
+        $crate::macros::Option::map(
+            {
+                #[allow(unused_imports)]
+                use $crate::macros::{CastRefSupported, CastRefUnsupported};
+
+                (&$crate::macros::CastRefProbe::<$from, dyn $as $($auto)*>::default()).handler()
+            },
+            $crate::registry::CastRefRecord::new::<$from, dyn $as $($auto)*>,
+        )
     };
 }
 
 #[macro_export]
 macro_rules! register_cast_mut {
     (struct $from:path : $as:path) => {
-        $crate::inventory::submit! {
-            #![crate = $crate]
-            $crate::registry::CastMutRecord::new::<$from, dyn $as>(
-                |item| {
-                    let item: &mut $from = $crate::macros::Any::downcast_mut(item)?;
-                    let item: &mut dyn $as = item;
+        $crate::__brace_cast_alloc! {
+            $crate::__brace_cast_submit! {
+                CastMutRecord => CAST_MUT_RECORDS,
+                $crate::register_cast_mut!(@records struct $from : $as)
+            }
+        }
+    };
 
-                    $crate::macros::Option::Some(item)
-                }
-            )
+    (@records struct $from:path : $as:path) => {
+        $crate::registry::CastRecordSet::new($crate::__brace_cast_auto!(
+            @list all register_cast_mut!(@record) struct $from : $as
+        ))
+    };
+
+    (@record [] struct $from:path : $as:path) => {
+        $crate::macros::Option::Some($crate::registry::CastMutRecord::new::<$from, dyn $as>(|item| {
+            let item: &mut $from = <dyn $crate::macros::Any>::downcast_mut(item)?;
+            let item: &mut dyn $as = item;
+
+            $crate::macros::Option::Some(item)
+        }))
+    };
+
+    (@record [$($auto:tt)*] struct $from:path : $as:path) => {
+        $crate::macros::Option::map(
+            {
+                #[allow(unused_imports)]
+                use $crate::macros::{CastMutSupported, CastMutUnsupported};
+
+                (&$crate::macros::CastMutProbe::<$from, dyn $as $($auto)*>::default()).handler()
+            },
+            $crate::registry::CastMutRecord::new::<$from, dyn $as $($auto)*>,
+        )
+    };
+}
+
+#[macro_export]
+macro_rules! register_cast_box {
+    (struct $from:path : $as:path) => {
+        $crate::__brace_cast_submit! {
+            CastBoxRecord => CAST_BOX_RECORDS,
+            $crate::register_cast_box!(@records struct $from : $as)
         }
     };
+
+    (@records struct $from:path : $as:path) => {
+        $crate::registry::CastRecordSet::new($crate::__brace_cast_auto!(
+            @list all register_cast_box!(@record) struct $from : $as
+        ))
+    };
+
+    (@record [] struct $from:path : $as:path) => {
+        $crate::macros::Option::Some($crate::registry::CastBoxRecord::new::<$from, dyn $as>(|item| {
+            let item: &mut $from = <dyn $crate::macros::Any>::downcast_mut(item)?;
+            let item: &mut dyn $as = item;
+
+            $crate::macros::Option::Some(item)
+        }))
+    };
+
+    (@record [$($auto:tt)*] struct $from:path : $as:path) => {
+        $crate::macros::Option::map(
+            {
+                #[allow(unused_imports)]
+                use $crate::macros::{CastBoxSupported, CastBoxUnsupported};
+
+                (&$crate::macros::CastBoxProbe::<$from, dyn $as $($auto)*>::default()).handler()
+            },
+            $crate::registry::CastBoxRecord::new::<$from, dyn $as $($auto)*>,
+        )
+    };
+}
+
+#[macro_export]
+macro_rules! register_cast_rc {
+    (struct $from:path : $as:path) => {
+        $crate::__brace_cast_submit! {
+            CastRcRecord => CAST_RC_RECORDS,
+            $crate::register_cast_rc!(@records struct $from : $as)
+        }
+    };
+
+    (@records struct $from:path : $as:path) => {
+        $crate::registry::CastRecordSet::new($crate::__brace_cast_auto!(
+            @list all register_cast_rc!(@record) struct $from : $as
+        ))
+    };
+
+    (@record [] struct $from:path : $as:path) => {
+        $crate::macros::Option::Some($crate::registry::CastRcRecord::new::<$from, dyn $as>(|item| {
+            let item: $crate::macros::Rc<$from> =
+                $crate::macros::Rc::<dyn $crate::macros::Any>::downcast(item)?;
+            let item: $crate::macros::Rc<dyn $as> = item;
+
+            $crate::macros::Result::Ok(item)
+        }))
+    };
+
+    (@record [$($auto:tt)*] struct $from:path : $as:path) => {
+        $crate::macros::Option::map(
+            {
+                #[allow(unused_imports)]
+                use $crate::macros::{CastRcSupported, CastRcUnsupported};
+
+                (&$crate::macros::CastRcProbe::<$from, dyn $as $($auto)*>::default()).handler()
+            },
+            $crate::registry::CastRcRecord::new::<$from, dyn $as $($auto)*>,
+        )
+    };
+}
+
+#[macro_export]
+macro_rules! register_cast_arc {
+    (struct $from:path : $as:path) => {
+        $crate::__brace_cast_submit! {
+            CastArcRecord => CAST_ARC_RECORDS,
+            $crate::register_cast_arc!(@records struct $from : $as)
+        }
+    };
+
+    (@records struct $from:path : $as:path) => {
+        $crate::registry::CastRecordSet::new($crate::__brace_cast_auto!(
+            @list shared register_cast_arc!(@record) struct $from : $as
+        ))
+    };
+
+    (@record [$($auto:tt)*] struct $from:path : $as:path) => {
+        $crate::macros::Option::map(
+            {
+                #[allow(unused_imports)]
+                use $crate::macros::{CastArcSupported, CastArcUnsupported};
+
+                (&$crate::macros::CastArcProbe::<$from, dyn $as $($auto)*>::default()).handler()
+            },
+            $crate::registry::CastArcRecord::new::<$from, dyn $as $($auto)*>,
+        )
+    };
+}
+
+#[macro_export]
+macro_rules! register_interface {
+    (trait $from:path = $id:expr) => {
+        impl $crate::CastInterface for dyn $from {
+            const ID: $crate::InterfaceId = $crate::InterfaceId::new($id);
+        }
+
+        $crate::__brace_cast_alloc! {
+            $crate::__brace_cast_submit! {
+                CastInterfaceRecord => CAST_INTERFACE_RECORDS,
+                $crate::register_interface!(@records trait $from)
+            }
+        }
+    };
+
+    (@records trait $from:path) => {
+        $crate::registry::CastRecordSet::new([$crate::macros::Option::Some(
+            $crate::registry::CastInterfaceRecord::new::<dyn $from>(),
+        )])
+    };
+}
+
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! export_casts {
+    () => {
+        #[export_name = "__brace_cast_exports"]
+        pub fn __brace_cast_exports() -> &'static $crate::registry::CastExports {
+            $crate::registry::exports()
+        }
+    };
+}
+
+/// Declares the cast table that the `static-registry` feature builds its registries from.
+///
+/// The table is exported under a fixed symbol, so a binary must contain exactly one
+/// `cast_table!`, usually in the crate that produces it. With none, or with a second one in
+/// another crate, linking fails on `brace_cast_static_registry_requires_one_cast_table`.
+#[cfg(feature = "static-registry")]
+#[macro_export]
+macro_rules! cast_table {
+    (trait $($iface:path),+; $($rest:tt)*) => {
+        $crate::cast_table!(@table [$($iface),+] $($rest)*);
+    };
+
+    ($($from:path : $($as:path),+);* $(;)?) => {
+        $crate::cast_table!(@table [] $($from : $($as),+);*);
+    };
+
+    (@table [$($iface:path),*] $($from:path : $($as:path),+);* $(;)?) => {
+        #[export_name = "brace_cast_static_registry_requires_one_cast_table"]
+        static CAST_TABLE: $crate::registry::CastTable = $crate::registry::CastTable::new(
+            &[$($(|| $crate::register_cast_ref!(@records struct $from : $as),)+)*],
+            &[$($(|| $crate::register_cast_mut!(@records struct $from : $as),)+)*],
+            &[$($(|| $crate::register_cast_box!(@records struct $from : $as),)+)*],
+            &[$($(|| $crate::register_cast_rc!(@records struct $from : $as),)+)*],
+            &[$($(|| $crate::register_cast_arc!(@records struct $from : $as),)+)*],
+            &[$(|| $crate::register_interface!(@records trait $iface),)*],
+        );
+    };
 }
 
 #[macro_export]
@@ -40,127 +695,428 @@ macro_rules! impl_cast_as {
     (struct $from:path : $as:path) => {
         $crate::impl_cast_as_ref!(struct $from : $as);
         $crate::impl_cast_as_mut!(struct $from : $as);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_as_box!(struct $from : $as);
+            $crate::impl_cast_as_rc!(struct $from : $as);
+            $crate::impl_cast_as_arc!(struct $from : $as);
+        }
     };
 
     (struct $from:path : $as:path $(, $also:path)+) => {
         $crate::impl_cast_as_ref!(struct $from : $as $(, $also)*);
         $crate::impl_cast_as_mut!(struct $from : $as $(, $also)*);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_as_box!(struct $from : $as $(, $also)*);
+            $crate::impl_cast_as_rc!(struct $from : $as $(, $also)*);
+            $crate::impl_cast_as_arc!(struct $from : $as $(, $also)*);
+        }
+    };
+
+    (impl $gen:tt struct $from:ty : $as:path $(, $also:path)* $(where $pred:tt)?) => {
+        $crate::impl_cast_as_ref!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+        $crate::impl_cast_as_mut!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_as_box!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+            $crate::impl_cast_as_rc!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+            $crate::impl_cast_as_arc!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+        }
+    };
+
+    (impl $gen:tt trait $from:path : $as:path $(, $also:path)* $(where $pred:tt)?) => {
+        $crate::impl_cast_as_ref!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+        $crate::impl_cast_as_mut!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_as_box!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+            $crate::impl_cast_as_rc!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+            $crate::impl_cast_as_arc!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+        }
     };
 
     (trait $from:path : $as:path) => {
         $crate::impl_cast_as_ref!(trait $from : $as);
         $crate::impl_cast_as_mut!(trait $from : $as);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_as_box!(trait $from : $as);
+            $crate::impl_cast_as_rc!(trait $from : $as);
+            $crate::impl_cast_as_arc!(trait $from : $as);
+        }
     };
 
     (trait $from:path : $as:path $(, $also:path)+) => {
         $crate::impl_cast_as_ref!(trait $from : $as $(, $also)*);
         $crate::impl_cast_as_mut!(trait $from : $as $(, $also)*);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_as_box!(trait $from : $as $(, $also)*);
+            $crate::impl_cast_as_rc!(trait $from : $as $(, $also)*);
+            $crate::impl_cast_as_arc!(trait $from : $as $(, $also)*);
+        }
     };
 }
 
 #[macro_export]
 macro_rules! impl_cast_as_ref {
-    (struct $from:path : $as:path) => {
-        $crate::register_cast_ref!(struct $from : $as);
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastAsRef<dyn $as $($auto)*> for $from
+        where
+            $from: $as, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_as_ref(&self) -> $crate::macros::Option<&(dyn $as $($auto)* + 'static)> {
+                $crate::macros::Option::Some(self as &(dyn $as $($auto)* + 'static))
+            }
+        }
 
-        impl $crate::CastAsRef<dyn $as> for $from
+        impl<$($gen)*> $crate::CastFromRef<dyn $as $($auto)*> for $from
         where
-            $from: $as,
+            $from: $as + $crate::Cast + 'static, for<'a> $from: $as $($auto)*, $($pred)*
         {
-            fn cast_as_ref(&self) -> $crate::macros::Option<&(dyn $as + 'static)> {
-                $crate::macros::Option::Some(self as &dyn $as)
+            fn cast_from_ref<'a>(
+                from: &'a (dyn $as $($auto)* + 'static),
+            ) -> $crate::macros::Option<&'a Self> {
+                <dyn $crate::macros::Any>::downcast_ref($crate::CastAsAny::cast_as_any_ref(from))
+            }
+
+            fn try_cast_from_ref<'a>(
+                from: &'a (dyn $as $($auto)* + 'static),
+            ) -> $crate::macros::Result<&'a Self, $crate::CastError> {
+                $crate::macros::try_downcast_ref(from)
+            }
+        }
+    };
+
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        $crate::__brace_cast_alloc! {
+            impl<$($gen)*> $crate::CastAsRef<dyn $as $($auto)*> for dyn $from $($auto)*
+            where
+                dyn $from: 'static, dyn $as: 'static, $($pred)*
+            {
+                fn cast_as_ref(&self) -> $crate::macros::Option<&(dyn $as $($auto)* + 'static)> {
+                    $crate::registry::cast_from_ref::<dyn $from $($auto)*, dyn $as $($auto)*>(self)
+                }
+
+                fn try_cast_as_ref(
+                    &self,
+                ) -> $crate::macros::Result<&(dyn $as $($auto)* + 'static), $crate::CastError> {
+                    $crate::registry::try_cast_from_ref::<
+                        dyn $from $($auto)*,
+                        dyn $as $($auto)*,
+                    >(self)
+                }
+            }
+
+            impl<$($gen)*> $crate::CastFromRef<dyn $as $($auto)*> for dyn $from $($auto)*
+            where
+                dyn $from: 'static, dyn $as: 'static, $($pred)*
+            {
+                fn cast_from_ref<'a>(
+                    from: &'a (dyn $as $($auto)* + 'static),
+                ) -> $crate::macros::Option<&'a Self> {
+                    $crate::registry::cast_from_ref::<dyn $as $($auto)*, dyn $from $($auto)*>(from)
+                }
+
+                fn try_cast_from_ref<'a>(
+                    from: &'a (dyn $as $($auto)* + 'static),
+                ) -> $crate::macros::Result<&'a Self, $crate::CastError> {
+                    $crate::registry::try_cast_from_ref::<
+                        dyn $as $($auto)*,
+                        dyn $from $($auto)*,
+                    >(from)
+                }
             }
         }
+    };
 
-        impl $crate::CastFromRef<dyn $as> for $from
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_as_ref, register_cast_ref, all; $($input)*);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_cast_as_mut {
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastAsMut<dyn $as $($auto)*> for $from
         where
-            $from: $as + $crate::Cast,
+            $from: $as, for<'a> $from: $as $($auto)*, $($pred)*
         {
-            fn cast_from_ref<'a>(from: &'a (dyn $as + 'static)) -> $crate::macros::Option<&'a Self> {
-                $crate::macros::Any::downcast_ref($crate::CastAsAny::cast_as_any_ref(from))
+            fn cast_as_mut(
+                &mut self,
+            ) -> $crate::macros::Option<&mut (dyn $as $($auto)* + 'static)> {
+                $crate::macros::Option::Some(self as &mut (dyn $as $($auto)* + 'static))
+            }
+        }
+
+        impl<$($gen)*> $crate::CastFromMut<dyn $as $($auto)*> for $from
+        where
+            $from: $as + $crate::Cast + 'static, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_from_mut<'a>(
+                from: &'a mut (dyn $as $($auto)* + 'static),
+            ) -> $crate::macros::Option<&'a mut Self> {
+                <dyn $crate::macros::Any>::downcast_mut($crate::CastAsAny::cast_as_any_mut(from))
+            }
+
+            fn try_cast_from_mut<'a>(
+                from: &'a mut (dyn $as $($auto)* + 'static),
+            ) -> $crate::macros::Result<&'a mut Self, $crate::CastError> {
+                $crate::macros::try_downcast_mut(from)
             }
         }
     };
 
-    (struct $from:path : $as:path $(, $also:path)+) => {
-        $crate::impl_cast_as_ref!(struct $from : $as);
-        $(
-            $crate::impl_cast_as_ref!(struct $from : $also);
-        )*
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        $crate::__brace_cast_alloc! {
+            impl<$($gen)*> $crate::CastAsMut<dyn $as $($auto)*> for dyn $from $($auto)*
+            where
+                dyn $from: 'static, dyn $as: 'static, $($pred)*
+            {
+                fn cast_as_mut(
+                    &mut self,
+                ) -> $crate::macros::Option<&mut (dyn $as $($auto)* + 'static)> {
+                    $crate::registry::cast_from_mut::<dyn $from $($auto)*, dyn $as $($auto)*>(self)
+                }
+
+                fn try_cast_as_mut(
+                    &mut self,
+                ) -> $crate::macros::Result<&mut (dyn $as $($auto)* + 'static), $crate::CastError> {
+                    $crate::registry::try_cast_from_mut::<
+                        dyn $from $($auto)*,
+                        dyn $as $($auto)*,
+                    >(self)
+                }
+            }
+
+            impl<$($gen)*> $crate::CastFromMut<dyn $as $($auto)*> for dyn $from $($auto)*
+            where
+                dyn $from: 'static, dyn $as: 'static, $($pred)*
+            {
+                fn cast_from_mut<'a>(
+                    from: &'a mut (dyn $as $($auto)* + 'static),
+                ) -> $crate::macros::Option<&'a mut Self> {
+                    $crate::registry::cast_from_mut::<dyn $as $($auto)*, dyn $from $($auto)*>(from)
+                }
+
+                fn try_cast_from_mut<'a>(
+                    from: &'a mut (dyn $as $($auto)* + 'static),
+                ) -> $crate::macros::Result<&'a mut Self, $crate::CastError> {
+                    $crate::registry::try_cast_from_mut::<
+                        dyn $as $($auto)*,
+                        dyn $from $($auto)*,
+                    >(from)
+                }
+            }
+        }
     };
 
-    (trait $from:path : $as:path) => {
-        impl $crate::CastAsRef<dyn $as> for dyn $from {
-            fn cast_as_ref(&self) -> $crate::macros::Option<&(dyn $as + 'static)> {
-                $crate::registry::cast_from_ref::<dyn $from, dyn $as>(self)
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_as_mut, register_cast_mut, all; $($input)*);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_cast_as_box {
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastAsBox<dyn $as $($auto)*> for $from
+        where
+            $from: $as, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_as_box(
+                self: $crate::macros::Box<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Box<dyn $as $($auto)*>,
+                $crate::macros::Box<Self>,
+            > {
+                $crate::macros::Result::Ok(self)
             }
         }
 
-        impl $crate::CastFromRef<dyn $as> for dyn $from {
-            fn cast_from_ref<'a>(from: &'a (dyn $as + 'static)) -> $crate::macros::Option<&'a Self> {
-                $crate::registry::cast_from_ref::<dyn $as, dyn $from>(from)
+        impl<$($gen)*> $crate::CastFromBox<dyn $as $($auto)*> for $from
+        where
+            $from: $as + $crate::Cast + 'static, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_from_box(
+                from: $crate::macros::Box<dyn $as $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Box<Self>,
+                $crate::macros::Box<dyn $as $($auto)*>,
+            > {
+                $crate::macros::downcast_box(from)
             }
         }
     };
 
-    (trait $from:path : $as:path $(, $also:path)+) => {
-        $crate::impl_cast_as_ref!(trait $from : $as);
-        $(
-            $crate::impl_cast_as_ref!(trait $from : $also);
-        )*
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastAsBox<dyn $as $($auto)*> for dyn $from $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_as_box(
+                self: $crate::macros::Box<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Box<dyn $as $($auto)*>,
+                $crate::macros::Box<Self>,
+            > {
+                $crate::registry::cast_from_box::<dyn $from $($auto)*, dyn $as $($auto)*>(self)
+            }
+        }
+
+        impl<$($gen)*> $crate::CastFromBox<dyn $as $($auto)*> for dyn $from $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_from_box(
+                from: $crate::macros::Box<dyn $as $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Box<Self>,
+                $crate::macros::Box<dyn $as $($auto)*>,
+            > {
+                $crate::registry::cast_from_box::<dyn $as $($auto)*, dyn $from $($auto)*>(from)
+            }
+        }
+    };
+
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_as_box, register_cast_box, all; $($input)*);
     };
 }
 
 #[macro_export]
-macro_rules! impl_cast_as_mut {
-    (struct $from:path : $as:path) => {
-        $crate::register_cast_mut!(struct $from : $as);
+macro_rules! impl_cast_as_rc {
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastAsRc<dyn $as $($auto)*> for $from
+        where
+            $from: $as, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_as_rc(
+                self: $crate::macros::Rc<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Rc<dyn $as $($auto)*>,
+                $crate::macros::Rc<Self>,
+            > {
+                $crate::macros::Result::Ok(self)
+            }
+        }
+
+        impl<$($gen)*> $crate::CastFromRc<dyn $as $($auto)*> for $from
+        where
+            $from: $as + $crate::Cast + 'static, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_from_rc(
+                from: $crate::macros::Rc<dyn $as $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Rc<Self>,
+                $crate::macros::Rc<dyn $as $($auto)*>,
+            > {
+                $crate::macros::downcast_rc(from)
+            }
+        }
+    };
 
-        impl $crate::CastAsMut<dyn $as> for $from
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastAsRc<dyn $as $($auto)*> for dyn $from $($auto)*
         where
-            $from: $as,
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
         {
-            fn cast_as_mut(&mut self) -> $crate::macros::Option<&mut (dyn $as + 'static)> {
-                $crate::macros::Option::Some(self as &mut dyn $as)
+            fn cast_as_rc(
+                self: $crate::macros::Rc<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Rc<dyn $as $($auto)*>,
+                $crate::macros::Rc<Self>,
+            > {
+                $crate::registry::cast_from_rc::<dyn $from $($auto)*, dyn $as $($auto)*>(self)
             }
         }
 
-        impl $crate::CastFromMut<dyn $as> for $from
+        impl<$($gen)*> $crate::CastFromRc<dyn $as $($auto)*> for dyn $from $($auto)*
         where
-            $from: $as + $crate::Cast,
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
         {
-            fn cast_from_mut<'a>(from: &'a mut (dyn $as + 'static)) -> $crate::macros::Option<&'a mut Self> {
-                $crate::macros::Any::downcast_mut($crate::CastAsAny::cast_as_any_mut(from))
+            fn cast_from_rc(
+                from: $crate::macros::Rc<dyn $as $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Rc<Self>,
+                $crate::macros::Rc<dyn $as $($auto)*>,
+            > {
+                $crate::registry::cast_from_rc::<dyn $as $($auto)*, dyn $from $($auto)*>(from)
             }
         }
     };
 
-    (struct $from:path : $as:path $(, $also:path)+) => {
-        $crate::impl_cast_as_mut!(struct $from : $as);
-        $(
-            $crate::impl_cast_as_mut!(struct $from : $also);
-        )*
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_as_rc, register_cast_rc, all; $($input)*);
     };
+}
 
-    (trait $from:path : $as:path) => {
-        impl $crate::CastAsMut<dyn $as> for dyn $from {
-            fn cast_as_mut(&mut self) -> $crate::macros::Option<&mut (dyn $as + 'static)> {
-                $crate::registry::cast_from_mut::<dyn $from, dyn $as>(self)
+#[macro_export]
+macro_rules! impl_cast_as_arc {
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastAsArc<dyn $as $($auto)*> for $from
+        where
+            for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_as_arc(
+                self: $crate::macros::Arc<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Arc<dyn $as $($auto)*>,
+                $crate::macros::Arc<Self>,
+            > {
+                $crate::macros::Result::Ok(self)
             }
         }
 
-        impl $crate::CastFromMut<dyn $as> for dyn $from {
-            fn cast_from_mut<'a>(from: &'a mut (dyn $as + 'static)) -> $crate::macros::Option<&'a mut Self> {
-                $crate::registry::cast_from_mut::<dyn $as, dyn $from>(from)
+        impl<$($gen)*> $crate::CastFromArc<dyn $as $($auto)*> for $from
+        where
+            for<'a> $from:
+                $as $($auto)* + $crate::Cast + 'static,
+            $($pred)*
+        {
+            fn cast_from_arc(
+                from: $crate::macros::Arc<dyn $as $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Arc<Self>,
+                $crate::macros::Arc<dyn $as $($auto)*>,
+            > {
+                $crate::macros::downcast_arc(from)
             }
         }
     };
 
-    (trait $from:path : $as:path $(, $also:path)+) => {
-        $crate::impl_cast_as_mut!(trait $from : $as);
-        $(
-            $crate::impl_cast_as_mut!(trait $from : $also);
-        )*
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastAsArc<dyn $as $($auto)*> for dyn $from $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_as_arc(
+                self: $crate::macros::Arc<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Arc<dyn $as $($auto)*>,
+                $crate::macros::Arc<Self>,
+            > {
+                $crate::registry::cast_from_arc::<
+                    dyn $from $($auto)*,
+                    dyn $as $($auto)*,
+                >(self)
+            }
+        }
+
+        impl<$($gen)*> $crate::CastFromArc<dyn $as $($auto)*> for dyn $from $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_from_arc(
+                from: $crate::macros::Arc<dyn $as $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Arc<Self>,
+                $crate::macros::Arc<dyn $as $($auto)*>,
+            > {
+                $crate::registry::cast_from_arc::<
+                    dyn $as $($auto)*,
+                    dyn $from $($auto)*,
+                >(from)
+            }
+        }
+    };
+
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_as_arc, register_cast_arc, shared; $($input)*);
     };
 }
 
@@ -169,126 +1125,401 @@ macro_rules! impl_cast_from {
     (struct $from:path : $as:path) => {
         $crate::impl_cast_from_ref!(struct $from : $as);
         $crate::impl_cast_from_mut!(struct $from : $as);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_from_box!(struct $from : $as);
+            $crate::impl_cast_from_rc!(struct $from : $as);
+            $crate::impl_cast_from_arc!(struct $from : $as);
+        }
     };
 
     (struct $from:path : $as:path $(, $also:path)+) => {
         $crate::impl_cast_from_ref!(struct $from : $as $(, $also)*);
         $crate::impl_cast_from_mut!(struct $from : $as $(, $also)*);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_from_box!(struct $from : $as $(, $also)*);
+            $crate::impl_cast_from_rc!(struct $from : $as $(, $also)*);
+            $crate::impl_cast_from_arc!(struct $from : $as $(, $also)*);
+        }
+    };
+
+    (impl $gen:tt struct $from:ty : $as:path $(, $also:path)* $(where $pred:tt)?) => {
+        $crate::impl_cast_from_ref!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+        $crate::impl_cast_from_mut!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_from_box!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+            $crate::impl_cast_from_rc!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+            $crate::impl_cast_from_arc!(impl $gen struct $from : $as $(, $also)* $(where $pred)?);
+        }
+    };
+
+    (impl $gen:tt trait $from:path : $as:path $(, $also:path)* $(where $pred:tt)?) => {
+        $crate::impl_cast_from_ref!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+        $crate::impl_cast_from_mut!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_from_box!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+            $crate::impl_cast_from_rc!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+            $crate::impl_cast_from_arc!(impl $gen trait $from : $as $(, $also)* $(where $pred)?);
+        }
     };
 
     (trait $from:path : $as:path) => {
         $crate::impl_cast_from_ref!(trait $from : $as);
         $crate::impl_cast_from_mut!(trait $from : $as);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_from_box!(trait $from : $as);
+            $crate::impl_cast_from_rc!(trait $from : $as);
+            $crate::impl_cast_from_arc!(trait $from : $as);
+        }
     };
 
     (trait $from:path : $as:path $(, $also:path)+) => {
         $crate::impl_cast_from_ref!(trait $from : $as $(, $also)*);
         $crate::impl_cast_from_mut!(trait $from : $as $(, $also)*);
+        $crate::__brace_cast_alloc! {
+            $crate::impl_cast_from_box!(trait $from : $as $(, $also)*);
+            $crate::impl_cast_from_rc!(trait $from : $as $(, $also)*);
+            $crate::impl_cast_from_arc!(trait $from : $as $(, $also)*);
+        }
     };
 }
 
 #[macro_export]
 macro_rules! impl_cast_from_ref {
-    (struct $from:path : $as:path) => {
-        $crate::register_cast_ref!(struct $from : $as);
-
-        impl $crate::CastFromRef<$from> for dyn $as
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastFromRef<$from> for dyn $as $($auto)*
         where
-            $from: $as,
+            $from: $as, for<'a> $from: $as $($auto)*, $($pred)*
         {
             fn cast_from_ref(from: &$from) -> $crate::macros::Option<&Self> {
-                $crate::macros::Option::Some(from as &dyn $as)
+                $crate::macros::Option::Some(from as &(dyn $as $($auto)* + 'static))
             }
         }
 
-        impl $crate::CastAsRef<$from> for dyn $as
+        impl<$($gen)*> $crate::CastAsRef<$from> for dyn $as $($auto)*
         where
-            $from: $as + $crate::Cast,
+            $from: $as + $crate::Cast + 'static, for<'a> $from: $as $($auto)*, $($pred)*
         {
             fn cast_as_ref(&self) -> $crate::macros::Option<&$from> {
-                $crate::macros::Any::downcast_ref($crate::CastAsAny::cast_as_any_ref(self))
+                <dyn $crate::macros::Any>::downcast_ref($crate::CastAsAny::cast_as_any_ref(self))
+            }
+
+            fn try_cast_as_ref(&self) -> $crate::macros::Result<&$from, $crate::CastError> {
+                $crate::macros::try_downcast_ref(self)
             }
         }
     };
 
-    (struct $from:path : $as:path $(, $also:path)+) => {
-        $crate::impl_cast_from_ref!(struct $from : $as);
-        $(
-            $crate::impl_cast_from_ref!(struct $from : $also);
-        )*
-    };
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        $crate::__brace_cast_alloc! {
+            impl<$($gen)*> $crate::CastFromRef<dyn $from $($auto)*> for dyn $as $($auto)*
+            where
+                dyn $from: 'static, dyn $as: 'static, $($pred)*
+            {
+                fn cast_from_ref<'a>(
+                    from: &'a (dyn $from $($auto)* + 'static),
+                ) -> $crate::macros::Option<&'a Self> {
+                    $crate::registry::cast_from_ref::<dyn $from $($auto)*, dyn $as $($auto)*>(from)
+                }
 
-    (trait $from:path : $as:path) => {
-        impl $crate::CastFromRef<dyn $from> for dyn $as {
-            fn cast_from_ref<'a>(from: &'a (dyn $from + 'static)) -> $crate::macros::Option<&'a Self> {
-                $crate::registry::cast_from_ref::<dyn $from, dyn $as>(from)
+                fn try_cast_from_ref<'a>(
+                    from: &'a (dyn $from $($auto)* + 'static),
+                ) -> $crate::macros::Result<&'a Self, $crate::CastError> {
+                    $crate::registry::try_cast_from_ref::<
+                        dyn $from $($auto)*,
+                        dyn $as $($auto)*,
+                    >(from)
+                }
             }
-        }
 
-        impl $crate::CastAsRef<dyn $from> for dyn $as {
-            fn cast_as_ref(&self) -> $crate::macros::Option<&(dyn $from + 'static)> {
-                $crate::registry::cast_from_ref::<dyn $as, dyn $from>(self)
+            impl<$($gen)*> $crate::CastAsRef<dyn $from $($auto)*> for dyn $as $($auto)*
+            where
+                dyn $from: 'static, dyn $as: 'static, $($pred)*
+            {
+                fn cast_as_ref(&self) -> $crate::macros::Option<&(dyn $from $($auto)* + 'static)> {
+                    $crate::registry::cast_from_ref::<dyn $as $($auto)*, dyn $from $($auto)*>(self)
+                }
+
+                fn try_cast_as_ref(
+                    &self,
+                ) -> $crate::macros::Result<&(dyn $from $($auto)* + 'static), $crate::CastError> {
+                    $crate::registry::try_cast_from_ref::<
+                        dyn $as $($auto)*,
+                        dyn $from $($auto)*,
+                    >(self)
+                }
             }
         }
     };
 
-    (trait $from:path : $as:path $(, $also:path)+) => {
-        $crate::impl_cast_from_ref!(trait $from : $as);
-        $(
-            $crate::impl_cast_from_ref!(trait $from : $also);
-        )*
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_from_ref, register_cast_ref, all; $($input)*);
     };
 }
 
 #[macro_export]
 macro_rules! impl_cast_from_mut {
-    (struct $from:path : $as:path) => {
-        $crate::register_cast_mut!(struct $from : $as);
-
-        impl $crate::CastFromMut<$from> for dyn $as
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastFromMut<$from> for dyn $as $($auto)*
         where
-            $from: $as,
+            $from: $as, for<'a> $from: $as $($auto)*, $($pred)*
         {
             fn cast_from_mut(from: &mut $from) -> $crate::macros::Option<&mut Self> {
-                $crate::macros::Option::Some(from as &mut dyn $as)
+                $crate::macros::Option::Some(from as &mut (dyn $as $($auto)* + 'static))
             }
         }
 
-        impl $crate::CastAsMut<$from> for dyn $as
+        impl<$($gen)*> $crate::CastAsMut<$from> for dyn $as $($auto)*
         where
-            $from: $as + $crate::Cast,
+            $from: $as + $crate::Cast + 'static, for<'a> $from: $as $($auto)*, $($pred)*
         {
             fn cast_as_mut(&mut self) -> $crate::macros::Option<&mut $from> {
-                $crate::macros::Any::downcast_mut($crate::CastAsAny::cast_as_any_mut(self))
+                <dyn $crate::macros::Any>::downcast_mut($crate::CastAsAny::cast_as_any_mut(self))
+            }
+
+            fn try_cast_as_mut(&mut self) -> $crate::macros::Result<&mut $from, $crate::CastError> {
+                $crate::macros::try_downcast_mut(self)
             }
         }
     };
 
-    (struct $from:path : $as:path $(, $also:path)+) => {
-        $crate::impl_cast_from_mut!(struct $from : $as);
-        $(
-            $crate::impl_cast_from_mut!(struct $from : $also);
-        )*
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        $crate::__brace_cast_alloc! {
+            impl<$($gen)*> $crate::CastFromMut<dyn $from $($auto)*> for dyn $as $($auto)*
+            where
+                dyn $from: 'static, dyn $as: 'static, $($pred)*
+            {
+                fn cast_from_mut<'a>(
+                    from: &'a mut (dyn $from $($auto)* + 'static),
+                ) -> $crate::macros::Option<&'a mut Self> {
+                    $crate::registry::cast_from_mut::<dyn $from $($auto)*, dyn $as $($auto)*>(from)
+                }
+
+                fn try_cast_from_mut<'a>(
+                    from: &'a mut (dyn $from $($auto)* + 'static),
+                ) -> $crate::macros::Result<&'a mut Self, $crate::CastError> {
+                    $crate::registry::try_cast_from_mut::<
+                        dyn $from $($auto)*,
+                        dyn $as $($auto)*,
+                    >(from)
+                }
+            }
+
+            impl<$($gen)*> $crate::CastAsMut<dyn $from $($auto)*> for dyn $as $($auto)*
+            where
+                dyn $from: 'static, dyn $as: 'static, $($pred)*
+            {
+                fn cast_as_mut(
+                    &mut self,
+                ) -> $crate::macros::Option<&mut (dyn $from $($auto)* + 'static)> {
+                    $crate::registry::cast_from_mut::<dyn $as $($auto)*, dyn $from $($auto)*>(self)
+                }
+
+                fn try_cast_as_mut(
+                    &mut self,
+                ) -> $crate::macros::Result<
+                    &mut (dyn $from $($auto)* + 'static),
+                    $crate::CastError,
+                > {
+                    $crate::registry::try_cast_from_mut::<
+                        dyn $as $($auto)*,
+                        dyn $from $($auto)*,
+                    >(self)
+                }
+            }
+        }
     };
 
-    (trait $from:path : $as:path) => {
-        impl $crate::CastFromMut<dyn $from> for dyn $as {
-            fn cast_from_mut<'a>(from: &'a mut (dyn $from + 'static)) -> $crate::macros::Option<&'a mut Self> {
-                $crate::registry::cast_from_mut::<dyn $from, dyn $as>(from)
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_from_mut, register_cast_mut, all; $($input)*);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_cast_from_box {
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastFromBox<$from> for dyn $as $($auto)*
+        where
+            $from: $as, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_from_box(
+                from: $crate::macros::Box<$from>,
+            ) -> $crate::macros::Result<$crate::macros::Box<Self>, $crate::macros::Box<$from>> {
+                $crate::macros::Result::Ok(from)
             }
         }
 
-        impl $crate::CastAsMut<dyn $from> for dyn $as {
-            fn cast_as_mut(&mut self) -> $crate::macros::Option<&mut (dyn $from + 'static)> {
-                $crate::registry::cast_from_mut::<dyn $as, dyn $from>(self)
+        impl<$($gen)*> $crate::CastAsBox<$from> for dyn $as $($auto)*
+        where
+            $from: $as + $crate::Cast + 'static, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_as_box(
+                self: $crate::macros::Box<Self>,
+            ) -> $crate::macros::Result<$crate::macros::Box<$from>, $crate::macros::Box<Self>> {
+                $crate::macros::downcast_box(self)
             }
         }
     };
 
-    (trait $from:path : $as:path $(, $also:path)+) => {
-        $crate::impl_cast_from_mut!(trait $from : $as);
-        $(
-            $crate::impl_cast_from_mut!(trait $from : $also);
-        )*
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastFromBox<dyn $from $($auto)*> for dyn $as $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_from_box(
+                from: $crate::macros::Box<dyn $from $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Box<Self>,
+                $crate::macros::Box<dyn $from $($auto)*>,
+            > {
+                $crate::registry::cast_from_box::<dyn $from $($auto)*, dyn $as $($auto)*>(from)
+            }
+        }
+
+        impl<$($gen)*> $crate::CastAsBox<dyn $from $($auto)*> for dyn $as $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_as_box(
+                self: $crate::macros::Box<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Box<dyn $from $($auto)*>,
+                $crate::macros::Box<Self>,
+            > {
+                $crate::registry::cast_from_box::<dyn $as $($auto)*, dyn $from $($auto)*>(self)
+            }
+        }
+    };
+
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_from_box, register_cast_box, all; $($input)*);
+    };
+}
+
+#[macro_export]
+macro_rules! impl_cast_from_rc {
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastFromRc<$from> for dyn $as $($auto)*
+        where
+            $from: $as, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_from_rc(
+                from: $crate::macros::Rc<$from>,
+            ) -> $crate::macros::Result<$crate::macros::Rc<Self>, $crate::macros::Rc<$from>> {
+                $crate::macros::Result::Ok(from)
+            }
+        }
+
+        impl<$($gen)*> $crate::CastAsRc<$from> for dyn $as $($auto)*
+        where
+            $from: $as + $crate::Cast + 'static, for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_as_rc(
+                self: $crate::macros::Rc<Self>,
+            ) -> $crate::macros::Result<$crate::macros::Rc<$from>, $crate::macros::Rc<Self>> {
+                $crate::macros::downcast_rc(self)
+            }
+        }
+    };
+
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastFromRc<dyn $from $($auto)*> for dyn $as $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_from_rc(
+                from: $crate::macros::Rc<dyn $from $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Rc<Self>,
+                $crate::macros::Rc<dyn $from $($auto)*>,
+            > {
+                $crate::registry::cast_from_rc::<dyn $from $($auto)*, dyn $as $($auto)*>(from)
+            }
+        }
+
+        impl<$($gen)*> $crate::CastAsRc<dyn $from $($auto)*> for dyn $as $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_as_rc(
+                self: $crate::macros::Rc<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Rc<dyn $from $($auto)*>,
+                $crate::macros::Rc<Self>,
+            > {
+                $crate::registry::cast_from_rc::<dyn $as $($auto)*, dyn $from $($auto)*>(self)
+            }
+        }
+    };
+
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_from_rc, register_cast_rc, all; $($input)*);
+    };
+}
+#[macro_export]
+macro_rules! impl_cast_from_arc {
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] struct $from:ty : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastFromArc<$from> for dyn $as $($auto)*
+        where
+            for<'a> $from: $as $($auto)*, $($pred)*
+        {
+            fn cast_from_arc(
+                from: $crate::macros::Arc<$from>,
+            ) -> $crate::macros::Result<$crate::macros::Arc<Self>, $crate::macros::Arc<$from>> {
+                $crate::macros::Result::Ok(from)
+            }
+        }
+
+        impl<$($gen)*> $crate::CastAsArc<$from> for dyn $as $($auto)*
+        where
+            for<'a> $from:
+                $as $($auto)* + $crate::Cast + 'static,
+            $($pred)*
+        {
+            fn cast_as_arc(
+                self: $crate::macros::Arc<Self>,
+            ) -> $crate::macros::Result<$crate::macros::Arc<$from>, $crate::macros::Arc<Self>> {
+                $crate::macros::downcast_arc(self)
+            }
+        }
+    };
+
+    (@auto [$($auto:tt)*] impl [$($gen:tt)*] trait $from:path : $as:path where [$($pred:tt)*]) => {
+        impl<$($gen)*> $crate::CastFromArc<dyn $from $($auto)*> for dyn $as $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_from_arc(
+                from: $crate::macros::Arc<dyn $from $($auto)*>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Arc<Self>,
+                $crate::macros::Arc<dyn $from $($auto)*>,
+            > {
+                $crate::registry::cast_from_arc::<
+                    dyn $from $($auto)*,
+                    dyn $as $($auto)*,
+                >(from)
+            }
+        }
+
+        impl<$($gen)*> $crate::CastAsArc<dyn $from $($auto)*> for dyn $as $($auto)*
+        where
+            dyn $from: 'static, dyn $as: 'static, $($pred)*
+        {
+            fn cast_as_arc(
+                self: $crate::macros::Arc<Self>,
+            ) -> $crate::macros::Result<
+                $crate::macros::Arc<dyn $from $($auto)*>,
+                $crate::macros::Arc<Self>,
+            > {
+                $crate::registry::cast_from_arc::<
+                    dyn $as $($auto)*,
+                    dyn $from $($auto)*,
+                >(self)
+            }
+        }
+    };
+
+    ($($input:tt)*) => {
+        $crate::__brace_cast_forward!(impl_cast_from_arc, register_cast_arc, shared; $($input)*);
     };
 }
@@ -1,124 +1,2337 @@
-use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::any::{type_name, Any, TypeId};
+use core::fmt;
+use core::hash::{BuildHasherDefault, Hasher};
+use core::mem;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::cell::Cell;
+#[cfg(feature = "std")]
+use std::thread::{self, LocalKey};
+#[cfg(feature = "std")]
+use std::time::Instant;
 
+use hashbrown::HashMap;
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
 use inventory::collect;
-use once_cell::sync::Lazy;
 
-use crate::Cast;
+use crate::sync::{Lazy, RwLock};
+use crate::{
+    Cast, CastAsAny, CastAsArc, CastAsBox, CastAsMut, CastAsRc, CastAsRef, CastError,
+    CastErrorKind, CastInterface, InterfaceId,
+};
 
 static CAST_REF_REGISTRY: Lazy<CastRefRegistry> = Lazy::new(CastRefRegistry::new);
 static CAST_MUT_REGISTRY: Lazy<CastMutRegistry> = Lazy::new(CastMutRegistry::new);
+static CAST_BOX_REGISTRY: Lazy<CastBoxRegistry> = Lazy::new(CastBoxRegistry::new);
+static CAST_RC_REGISTRY: Lazy<CastRcRegistry> = Lazy::new(CastRcRegistry::new);
+static CAST_ARC_REGISTRY: Lazy<CastArcRegistry> = Lazy::new(CastArcRegistry::new);
+static CAST_INTERFACE_REGISTRY: Lazy<CastInterfaceRegistry> = Lazy::new(CastInterfaceRegistry::new);
+static CAST_EXPORTS: Lazy<CastExports> = Lazy::new(CastExports::collect);
 
-collect!(CastRefRecord);
-collect!(CastMutRecord);
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
+collect!(CastRecordSet<CastRefRecord>);
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
+collect!(CastRecordSet<CastMutRecord>);
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
+collect!(CastRecordSet<CastBoxRecord>);
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
+collect!(CastRecordSet<CastRcRecord>);
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
+collect!(CastRecordSet<CastArcRecord>);
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
+collect!(CastRecordSet<CastInterfaceRecord>);
+
+#[cfg(all(feature = "linkme", not(feature = "static-registry")))]
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static CAST_REF_RECORDS: [fn() -> CastRecordSet<CastRefRecord>] = [..];
+#[cfg(all(feature = "linkme", not(feature = "static-registry")))]
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static CAST_MUT_RECORDS: [fn() -> CastRecordSet<CastMutRecord>] = [..];
+#[cfg(all(feature = "linkme", not(feature = "static-registry")))]
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static CAST_BOX_RECORDS: [fn() -> CastRecordSet<CastBoxRecord>] = [..];
+#[cfg(all(feature = "linkme", not(feature = "static-registry")))]
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static CAST_RC_RECORDS: [fn() -> CastRecordSet<CastRcRecord>] = [..];
+#[cfg(all(feature = "linkme", not(feature = "static-registry")))]
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static CAST_ARC_RECORDS: [fn() -> CastRecordSet<CastArcRecord>] = [..];
+#[cfg(all(feature = "linkme", not(feature = "static-registry")))]
+#[doc(hidden)]
+#[linkme::distributed_slice]
+pub static CAST_INTERFACE_RECORDS: [fn() -> CastRecordSet<CastInterfaceRecord>] = [..];
+
+#[cfg(feature = "std")]
+thread_local! {
+    static LOCAL_REF_REGISTRY: Cell<*const CastRefRegistry> = const { Cell::new(ptr::null()) };
+    static LOCAL_MUT_REGISTRY: Cell<*const CastMutRegistry> = const { Cell::new(ptr::null()) };
+    static LOCAL_BOX_REGISTRY: Cell<*const CastBoxRegistry> = const { Cell::new(ptr::null()) };
+    static LOCAL_RC_REGISTRY: Cell<*const CastRcRegistry> = const { Cell::new(ptr::null()) };
+    static LOCAL_ARC_REGISTRY: Cell<*const CastArcRegistry> = const { Cell::new(ptr::null()) };
+}
+
+#[cfg(feature = "static-registry")]
+extern "Rust" {
+    #[link_name = "brace_cast_static_registry_requires_one_cast_table"]
+    static CAST_TABLE: CastTable;
+}
+
+pub const CAST_EXPORTS_SYMBOL: &[u8] = b"__brace_cast_exports";
+
+pub type CastExportsFn = fn() -> &'static CastExports;
 
 pub type CastRefHandler<T> = fn(&dyn Any) -> Option<&T>;
 pub type CastMutHandler<T> = fn(&mut dyn Any) -> Option<&mut T>;
+pub type CastBoxHandler<T> = fn(&mut dyn Any) -> Option<&mut T>;
+pub type CastRcHandler<T> = fn(Rc<dyn Any>) -> Result<Rc<T>, Rc<dyn Any>>;
+pub type CastArcHandler<T> =
+    fn(Arc<dyn Any + Send + Sync>) -> Result<Arc<T>, Arc<dyn Any + Send + Sync>>;
+
+/// Registers the ref, mut, box and rc casts from `S` to `T`.
+///
+/// `Arc` casts need `S: Send + Sync` and are registered separately with [`register_arc`].
+pub fn register<S, T>()
+where
+    S: CastAsRef<T> + CastAsMut<T> + CastAsBox<T> + CastAsRc<T> + 'static,
+    T: ?Sized + 'static,
+{
+    register_ref::<S, T>();
+    register_mut::<S, T>();
+    register_box::<S, T>();
+    register_rc::<S, T>();
+}
+
+pub fn register_ref<S, T>()
+where
+    S: CastAsRef<T> + 'static,
+    T: ?Sized + 'static,
+{
+    CAST_REF_REGISTRY.register(CastRefRecord::new::<S, T>(|item| {
+        let item: &S = item.downcast_ref()?;
+
+        item.cast_as_ref()
+    }));
+}
+
+pub fn register_mut<S, T>()
+where
+    S: CastAsMut<T> + 'static,
+    T: ?Sized + 'static,
+{
+    CAST_MUT_REGISTRY.register(CastMutRecord::new::<S, T>(|item| {
+        let item: &mut S = item.downcast_mut()?;
+
+        item.cast_as_mut()
+    }));
+}
+
+pub fn register_box<S, T>()
+where
+    S: CastAsMut<T> + 'static,
+    T: ?Sized + 'static,
+{
+    CAST_BOX_REGISTRY.register(CastBoxRecord::new::<S, T>(|item| {
+        let item: &mut S = item.downcast_mut()?;
+
+        item.cast_as_mut()
+    }));
+}
+
+pub fn register_rc<S, T>()
+where
+    S: CastAsRc<T> + 'static,
+    T: ?Sized + 'static,
+{
+    CAST_RC_REGISTRY.register(CastRcRecord::new::<S, T>(|item| {
+        let item: Rc<S> = item.downcast()?;
+
+        item.cast_as_rc().map_err(|item| item as Rc<dyn Any>)
+    }));
+}
+
+pub fn register_arc<S, T>()
+where
+    S: CastAsArc<T> + Send + Sync + 'static,
+    T: ?Sized + 'static,
+{
+    CAST_ARC_REGISTRY.register(CastArcRecord::new::<S, T>(|item| {
+        let item: Arc<S> = item.downcast()?;
+
+        item.cast_as_arc()
+            .map_err(|item| item as Arc<dyn Any + Send + Sync>)
+    }));
+}
+
+#[cfg(feature = "std")]
+pub fn with_ref_registry<F, R>(registry: &CastRefRegistry, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = LocalRegistry::install(&LOCAL_REF_REGISTRY, registry);
+
+    f()
+}
+
+#[cfg(feature = "std")]
+pub fn with_mut_registry<F, R>(registry: &CastMutRegistry, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = LocalRegistry::install(&LOCAL_MUT_REGISTRY, registry);
+
+    f()
+}
+
+#[cfg(feature = "std")]
+pub fn with_box_registry<F, R>(registry: &CastBoxRegistry, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = LocalRegistry::install(&LOCAL_BOX_REGISTRY, registry);
+
+    f()
+}
+
+#[cfg(feature = "std")]
+pub fn with_rc_registry<F, R>(registry: &CastRcRegistry, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = LocalRegistry::install(&LOCAL_RC_REGISTRY, registry);
+
+    f()
+}
+
+#[cfg(feature = "std")]
+pub fn with_arc_registry<F, R>(registry: &CastArcRegistry, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = LocalRegistry::install(&LOCAL_ARC_REGISTRY, registry);
+
+    f()
+}
+
+// Safety: `T` must be the concrete type behind `from`. It is then sized, so the thin data
+// pointer is all of it.
+unsafe fn downcast_ref<T>(from: &dyn Any) -> &T
+where
+    T: ?Sized + 'static,
+{
+    let item = from as *const dyn Any as *const ();
+
+    &*mem::transmute_copy::<*const (), *const T>(&item)
+}
+
+unsafe fn downcast_mut<T>(from: &mut dyn Any) -> &mut T
+where
+    T: ?Sized + 'static,
+{
+    let item = from as *mut dyn Any as *mut ();
+
+    &mut *mem::transmute_copy::<*mut (), *mut T>(&item)
+}
+
+pub fn cast_from_ref<S, T>(from: &S) -> Option<&T>
+where
+    S: Cast + ?Sized + 'static,
+    T: ?Sized + 'static,
+{
+    active_ref_registry(|registry| registry.cast_from_ref(from))
+}
+
+pub fn cast_from_mut<S, T>(from: &mut S) -> Option<&mut T>
+where
+    S: Cast + ?Sized + 'static,
+    T: ?Sized + 'static,
+{
+    active_mut_registry(move |registry| registry.cast_from_mut(from))
+}
+
+pub fn try_cast_from_ref<S, T>(from: &S) -> Result<&T, CastError>
+where
+    S: Cast + ?Sized + 'static,
+    T: ?Sized + 'static,
+{
+    active_ref_registry(|registry| registry.try_cast_from_ref(from))
+}
+
+pub fn try_cast_from_mut<S, T>(from: &mut S) -> Result<&mut T, CastError>
+where
+    S: Cast + ?Sized + 'static,
+    T: ?Sized + 'static,
+{
+    active_mut_registry(move |registry| registry.try_cast_from_mut(from))
+}
+
+pub fn cast_any_ref<T>(from: &dyn Any) -> Option<&T>
+where
+    T: ?Sized + 'static,
+{
+    active_ref_registry(|registry| registry.cast_any_ref(from))
+}
+
+pub fn cast_any_mut<T>(from: &mut dyn Any) -> Option<&mut T>
+where
+    T: ?Sized + 'static,
+{
+    active_mut_registry(move |registry| registry.cast_any_mut(from))
+}
+
+pub fn try_cast_any_ref<T>(from: &dyn Any) -> Result<&T, CastError>
+where
+    T: ?Sized + 'static,
+{
+    active_ref_registry(|registry| registry.try_cast_any_ref(from))
+}
+
+pub fn try_cast_any_mut<T>(from: &mut dyn Any) -> Result<&mut T, CastError>
+where
+    T: ?Sized + 'static,
+{
+    active_mut_registry(move |registry| registry.try_cast_any_mut(from))
+}
+
+pub fn implementors_of<T>() -> impl Iterator<Item = CastRecordView>
+where
+    T: ?Sized + 'static,
+{
+    active_ref_registry(|registry| registry.implementors_of::<T>())
+}
+
+pub fn targets_of<S>(from: &S) -> impl Iterator<Item = CastRecordView>
+where
+    S: Cast + ?Sized + 'static,
+{
+    active_ref_registry(|registry| registry.targets_of(from))
+}
+
+pub fn can_cast<S, T>() -> bool
+where
+    S: 'static,
+    T: ?Sized + 'static,
+{
+    active_ref_registry(|registry| registry.can_cast::<S, T>())
+}
+
+pub fn cast_from_box<S, T>(from: Box<S>) -> Result<Box<T>, Box<S>>
+where
+    S: Cast + ?Sized + 'static,
+    T: ?Sized + 'static,
+{
+    active_box_registry(move |registry| registry.cast_from_box(from))
+}
+
+pub fn cast_any_box<T>(from: Box<dyn Any>) -> Result<Box<T>, Box<dyn Any>>
+where
+    T: ?Sized + 'static,
+{
+    active_box_registry(move |registry| registry.cast_any_box(from))
+}
+
+pub fn register_interface<T>()
+where
+    T: CastInterface + ?Sized + 'static,
+{
+    CAST_INTERFACE_REGISTRY.register(CastInterfaceRecord::new::<T>());
+}
+
+pub fn interface(id: InterfaceId) -> Option<&'static CastInterfaceRecord> {
+    CAST_INTERFACE_REGISTRY.get(id)
+}
+
+pub fn interfaces() -> impl Iterator<Item = &'static CastInterfaceRecord> {
+    CAST_INTERFACE_REGISTRY.records()
+}
+
+pub fn cast_interface_ref<S, T>(from: &S) -> Option<&T>
+where
+    S: Cast + ?Sized + 'static,
+    T: CastInterface + ?Sized + 'static,
+{
+    let mut out: Option<&T> = None;
+
+    // The artifact that owns `from` only writes an `Option<&T>` for the interface `T::ID`.
+    unsafe { from.cast_query_ref(T::ID, &mut out as *mut Option<&T> as *mut ()) };
+
+    out
+}
+
+pub fn cast_interface_mut<S, T>(from: &mut S) -> Option<&mut T>
+where
+    S: Cast + ?Sized + 'static,
+    T: CastInterface + ?Sized + 'static,
+{
+    let mut out: Option<&mut T> = None;
+
+    // The artifact that owns `from` only writes an `Option<&mut T>` for the interface `T::ID`.
+    unsafe { from.cast_query_mut(T::ID, &mut out as *mut Option<&mut T> as *mut ()) };
+
+    out
+}
+
+pub fn cast_from_rc<S, T>(from: Rc<S>) -> Result<Rc<T>, Rc<S>>
+where
+    S: Cast + ?Sized + 'static,
+    T: ?Sized + 'static,
+{
+    active_rc_registry(move |registry| registry.cast_from_rc(from))
+}
+
+pub fn cast_from_arc<S, T>(from: Arc<S>) -> Result<Arc<T>, Arc<S>>
+where
+    S: Cast + Send + Sync + ?Sized + 'static,
+    T: ?Sized + 'static,
+{
+    active_arc_registry(move |registry| registry.cast_from_arc(from))
+}
+
+/// Reports duplicate submitted records of every kind and ref/mut records without a counterpart.
+///
+/// Runtime `register*` calls replace existing records by design, so they only take part in the
+/// ref/mut parity check, which runs against the active registries.
+pub fn validate() -> CastValidationReport {
+    let mut report =
+        CastValidationReport::collect(CastRecordKind::Ref, submitted::<CastRefRecord>());
+
+    report.merge(CastValidationReport::collect(
+        CastRecordKind::Mut,
+        submitted::<CastMutRecord>(),
+    ));
+    report.merge(CastValidationReport::collect(
+        CastRecordKind::Box,
+        submitted::<CastBoxRecord>(),
+    ));
+    report.merge(CastValidationReport::collect(
+        CastRecordKind::Rc,
+        submitted::<CastRcRecord>(),
+    ));
+    report.merge(CastValidationReport::collect(
+        CastRecordKind::Arc,
+        submitted::<CastArcRecord>(),
+    ));
+    report.parity = parity();
+    report
+}
+
+pub fn parity() -> CastParityReport {
+    active_ref_registry(|refs| active_mut_registry(|muts| CastParityReport::new(refs, muts)))
+}
+
+pub fn assert_parity() {
+    parity().assert_valid();
+}
+
+pub fn init() -> CastInitStats {
+    CastInitStats::collect(|| {
+        Lazy::force(&CAST_REF_REGISTRY);
+        Lazy::force(&CAST_MUT_REGISTRY);
+        Lazy::force(&CAST_BOX_REGISTRY);
+        Lazy::force(&CAST_RC_REGISTRY);
+        Lazy::force(&CAST_ARC_REGISTRY);
+        Lazy::force(&CAST_INTERFACE_REGISTRY);
+    })
+}
+
+#[cfg(feature = "std")]
+pub fn init_parallel() -> CastInitStats {
+    CastInitStats::collect(|| {
+        thread::scope(|scope| {
+            scope.spawn(|| Lazy::force(&CAST_REF_REGISTRY));
+            scope.spawn(|| Lazy::force(&CAST_MUT_REGISTRY));
+            scope.spawn(|| Lazy::force(&CAST_BOX_REGISTRY));
+            scope.spawn(|| Lazy::force(&CAST_RC_REGISTRY));
+            scope.spawn(|| Lazy::force(&CAST_ARC_REGISTRY));
+            scope.spawn(|| Lazy::force(&CAST_INTERFACE_REGISTRY));
+        })
+    })
+}
+
+pub fn is_initialized() -> bool {
+    Lazy::get(&CAST_REF_REGISTRY).is_some()
+        && Lazy::get(&CAST_MUT_REGISTRY).is_some()
+        && Lazy::get(&CAST_BOX_REGISTRY).is_some()
+        && Lazy::get(&CAST_RC_REGISTRY).is_some()
+        && Lazy::get(&CAST_ARC_REGISTRY).is_some()
+        && Lazy::get(&CAST_INTERFACE_REGISTRY).is_some()
+}
+
+pub fn exports() -> &'static CastExports {
+    &CAST_EXPORTS
+}
+
+/// Adds the records exported by a loaded library to the global ref and mut registries.
+///
+/// Dropping the returned guard removes the records again, but never frees them: lookups and
+/// errors may still hold references into them and into the library's type names.
+///
+/// # Safety
+///
+/// The library that `exports` came from must stay loaded for the rest of the process, even after
+/// the guard is dropped. It must also be built by the same compiler against the same interface
+/// definitions as this artifact, because records for a shared interface run the library's
+/// handler under this artifact's `TypeId` for it.
+pub unsafe fn import(exports: &'static CastExports) -> CastImport {
+    let targets = exports
+        .interfaces
+        .iter()
+        .filter_map(|exported| Some((exported, interface(exported.id)?)))
+        .filter(|(exported, interface)| exported.target_name == interface.target_name)
+        .map(|(exported, interface)| (exported.target, interface))
+        .collect::<Vec<_>>();
+
+    CastImport {
+        refs: import_records(&CAST_REF_REGISTRY.0, &exports.refs, &targets, CastRefRecord),
+        muts: import_records(&CAST_MUT_REGISTRY.0, &exports.muts, &targets, CastMutRecord),
+    }
+}
+
+fn active_ref_registry<F, R>(f: F) -> R
+where
+    F: FnOnce(&CastRefRegistry) -> R,
+{
+    #[cfg(feature = "std")]
+    return LocalRegistry::with(&LOCAL_REF_REGISTRY, &CAST_REF_REGISTRY, f);
+
+    #[cfg(not(feature = "std"))]
+    return f(&CAST_REF_REGISTRY);
+}
+
+fn active_mut_registry<F, R>(f: F) -> R
+where
+    F: FnOnce(&CastMutRegistry) -> R,
+{
+    #[cfg(feature = "std")]
+    return LocalRegistry::with(&LOCAL_MUT_REGISTRY, &CAST_MUT_REGISTRY, f);
+
+    #[cfg(not(feature = "std"))]
+    return f(&CAST_MUT_REGISTRY);
+}
+
+fn active_box_registry<F, R>(f: F) -> R
+where
+    F: FnOnce(&CastBoxRegistry) -> R,
+{
+    #[cfg(feature = "std")]
+    return LocalRegistry::with(&LOCAL_BOX_REGISTRY, &CAST_BOX_REGISTRY, f);
+
+    #[cfg(not(feature = "std"))]
+    return f(&CAST_BOX_REGISTRY);
+}
+
+fn active_rc_registry<F, R>(f: F) -> R
+where
+    F: FnOnce(&CastRcRegistry) -> R,
+{
+    #[cfg(feature = "std")]
+    return LocalRegistry::with(&LOCAL_RC_REGISTRY, &CAST_RC_REGISTRY, f);
+
+    #[cfg(not(feature = "std"))]
+    return f(&CAST_RC_REGISTRY);
+}
+
+fn active_arc_registry<F, R>(f: F) -> R
+where
+    F: FnOnce(&CastArcRegistry) -> R,
+{
+    #[cfg(feature = "std")]
+    return LocalRegistry::with(&LOCAL_ARC_REGISTRY, &CAST_ARC_REGISTRY, f);
+
+    #[cfg(not(feature = "std"))]
+    return f(&CAST_ARC_REGISTRY);
+}
+
+#[cfg(not(any(feature = "linkme", feature = "static-registry")))]
+fn submitted<R>() -> impl Iterator<Item = &'static R>
+where
+    CastRecordSet<R>: inventory::Collect,
+{
+    inventory::iter::<CastRecordSet<R>>
+        .into_iter()
+        .flat_map(|set| set.0.iter())
+}
+
+#[cfg(any(feature = "linkme", feature = "static-registry"))]
+fn submitted<R>() -> impl Iterator<Item = &'static R>
+where
+    R: Submitted,
+{
+    R::submitted().iter()
+}
+
+#[cfg(feature = "static-registry")]
+fn cast_table() -> &'static CastTable {
+    // The symbol is defined by the `cast_table!` invocation in the final binary.
+    unsafe { &CAST_TABLE }
+}
+
+pub(crate) unsafe fn query_ref<S>(from: &S, id: InterfaceId, out: *mut ()) -> bool
+where
+    S: 'static,
+{
+    let interface = match CAST_INTERFACE_REGISTRY.get(id) {
+        Some(interface) => interface,
+        None => return false,
+    };
+    let key = (interface.target, TypeId::of::<S>());
+
+    active_ref_registry(|registry| match registry.0.get(&key) {
+        Some(record) => (interface.query_ref)(record, from, out),
+        None => false,
+    })
+}
+
+pub(crate) unsafe fn query_mut<S>(from: &mut S, id: InterfaceId, out: *mut ()) -> bool
+where
+    S: 'static,
+{
+    let interface = match CAST_INTERFACE_REGISTRY.get(id) {
+        Some(interface) => interface,
+        None => return false,
+    };
+    let key = (interface.target, TypeId::of::<S>());
+
+    active_mut_registry(|registry| match registry.0.get(&key) {
+        Some(record) => (interface.query_mut)(record, from, out),
+        None => false,
+    })
+}
+
+pub(crate) fn query_ptr<S>(from: &S, id: InterfaceId) -> *const ()
+where
+    S: 'static,
+{
+    let interface = match CAST_INTERFACE_REGISTRY.get(id) {
+        Some(interface) => interface,
+        None => return ptr::null(),
+    };
+    let key = (interface.target, TypeId::of::<S>());
+
+    active_ref_registry(|registry| match registry.0.get(&key) {
+        Some(record) => (interface.query_ptr)(record, from),
+        None => ptr::null(),
+    })
+}
+
+fn import_records<R>(
+    map: &RecordMap<R>,
+    records: &[&'static R],
+    targets: &[(TypeId, &'static CastInterfaceRecord)],
+    new: fn(CastRecordView, Option<ErasedHandler>) -> R,
+) -> Vec<&'static R>
+where
+    R: Record,
+{
+    let mut imported = Vec::new();
+
+    for &rec in records {
+        let view = rec.view();
+
+        // The exporter may have been built with a different `TypeId` for the same interface, so
+        // its records are keyed by the interface as this artifact sees it. They are leaked like
+        // every other runtime record, since removing them does not end outstanding borrows.
+        let rec = match targets.iter().find(|(target, _)| *target == view.target) {
+            Some((_, interface)) if interface.target != view.target => {
+                let view = view.retarget(interface);
+
+                if map.get(&(view.target, view.source)).is_some() {
+                    continue;
+                }
+
+                Box::leak(Box::new(new(view, rec.erased())))
+            }
+            _ => rec,
+        };
+
+        if map.import(rec) {
+            imported.push(rec);
+        }
+    }
+
+    imported
+}
+
+unsafe fn query_ref_as<T>(record: &CastRefRecord, from: &dyn Any, out: *mut ()) -> bool
+where
+    T: ?Sized + 'static,
+{
+    match record.handler::<T>().and_then(|cast| cast(from)) {
+        Some(item) => {
+            out.cast::<Option<&T>>().write(Some(item));
+
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe fn query_mut_as<T>(record: &CastMutRecord, from: &mut dyn Any, out: *mut ()) -> bool
+where
+    T: ?Sized + 'static,
+{
+    match record.handler::<T>().and_then(|cast| cast(from)) {
+        Some(item) => {
+            out.cast::<Option<&mut T>>().write(Some(item));
+
+            true
+        }
+        None => false,
+    }
+}
+
+fn query_ptr_as<T>(record: &CastRefRecord, from: &dyn Any) -> *const ()
+where
+    T: ?Sized + 'static,
+{
+    match record.handler::<T>().and_then(|cast| cast(from)) {
+        Some(item) => item as *const T as *const (),
+        None => ptr::null(),
+    }
+}
+
+fn timed<F>(f: F) -> Option<Duration>
+where
+    F: FnOnce(),
+{
+    #[cfg(feature = "std")]
+    let start = Instant::now();
+
+    f();
+
+    #[cfg(feature = "std")]
+    return Some(start.elapsed());
+
+    #[cfg(not(feature = "std"))]
+    return None;
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CastRecordView {
+    source: TypeId,
+    target: TypeId,
+    source_name: &'static str,
+    target_name: &'static str,
+}
+
+impl CastRecordView {
+    fn new<S, T>() -> Self
+    where
+        S: ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        Self {
+            source: TypeId::of::<S>(),
+            target: TypeId::of::<T>(),
+            source_name: type_name::<S>(),
+            target_name: type_name::<T>(),
+        }
+    }
+
+    pub fn source_id(&self) -> TypeId {
+        self.source
+    }
+
+    pub fn target_id(&self) -> TypeId {
+        self.target
+    }
+
+    pub fn source_name(&self) -> &'static str {
+        self.source_name
+    }
+
+    pub fn target_name(&self) -> &'static str {
+        self.target_name
+    }
+
+    fn key(&self) -> (TypeId, TypeId) {
+        (self.target, self.source)
+    }
+
+    fn retarget(self, interface: &CastInterfaceRecord) -> Self {
+        Self {
+            target: interface.target,
+            target_name: interface.target_name,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for CastRecordView {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {}", self.source_name, self.target_name)
+    }
+}
+
+/// The records one declaration submits for a kind: the plain cast and whichever `Send`/`Sync`
+/// variants the source type supports.
+pub struct CastRecordSet<R: 'static>(Vec<R>);
+
+impl<R> CastRecordSet<R> {
+    pub fn new<I>(records: I) -> Self
+    where
+        I: IntoIterator<Item = Option<R>>,
+    {
+        Self(records.into_iter().flatten().collect())
+    }
+}
+
+pub struct CastRefRecord(CastRecordView, Option<ErasedHandler>);
+
+impl Record for CastRefRecord {
+    fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn erased(&self) -> Option<ErasedHandler> {
+        self.1
+    }
+}
+
+impl CastRefRecord {
+    pub fn new<S, T>(handler: CastRefHandler<T>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self::optional::<S, T>(Some(handler))
+    }
+
+    pub fn optional<S, T>(handler: Option<CastRefHandler<T>>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self(CastRecordView::new::<S, T>(), handler.map(erase))
+    }
+
+    pub fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn handler<T>(&self) -> Option<CastRefHandler<T>>
+    where
+        T: ?Sized + 'static,
+    {
+        // The handler was erased from a `CastRefHandler<T>` for this record's target.
+        unsafe { restore::<T, _>(self.0, self.1) }
+    }
+}
+
+pub struct CastMutRecord(CastRecordView, Option<ErasedHandler>);
+
+impl Record for CastMutRecord {
+    fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn erased(&self) -> Option<ErasedHandler> {
+        self.1
+    }
+}
+
+impl CastMutRecord {
+    pub fn new<S, T>(handler: CastMutHandler<T>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self::optional::<S, T>(Some(handler))
+    }
+
+    pub fn optional<S, T>(handler: Option<CastMutHandler<T>>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self(CastRecordView::new::<S, T>(), handler.map(erase))
+    }
+
+    pub fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn handler<T>(&self) -> Option<CastMutHandler<T>>
+    where
+        T: ?Sized + 'static,
+    {
+        // The handler was erased from a `CastMutHandler<T>` for this record's target.
+        unsafe { restore::<T, _>(self.0, self.1) }
+    }
+}
+
+pub struct CastBoxRecord(CastRecordView, Option<ErasedHandler>);
+
+impl Record for CastBoxRecord {
+    fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn erased(&self) -> Option<ErasedHandler> {
+        self.1
+    }
+}
+
+impl CastBoxRecord {
+    pub fn new<S, T>(handler: CastBoxHandler<T>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self::optional::<S, T>(Some(handler))
+    }
+
+    pub fn optional<S, T>(handler: Option<CastBoxHandler<T>>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self(CastRecordView::new::<S, T>(), handler.map(erase))
+    }
+
+    pub fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn handler<T>(&self) -> Option<CastBoxHandler<T>>
+    where
+        T: ?Sized + 'static,
+    {
+        // The handler was erased from a `CastBoxHandler<T>` for this record's target.
+        unsafe { restore::<T, _>(self.0, self.1) }
+    }
+}
+
+pub struct CastRcRecord(CastRecordView, Option<ErasedHandler>);
+
+impl Record for CastRcRecord {
+    fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn erased(&self) -> Option<ErasedHandler> {
+        self.1
+    }
+}
+
+impl CastRcRecord {
+    pub fn new<S, T>(handler: CastRcHandler<T>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self::optional::<S, T>(Some(handler))
+    }
+
+    pub fn optional<S, T>(handler: Option<CastRcHandler<T>>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self(CastRecordView::new::<S, T>(), handler.map(erase))
+    }
+
+    pub fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn handler<T>(&self) -> Option<CastRcHandler<T>>
+    where
+        T: ?Sized + 'static,
+    {
+        // The handler was erased from a `CastRcHandler<T>` for this record's target.
+        unsafe { restore::<T, _>(self.0, self.1) }
+    }
+}
+
+pub struct CastArcRecord(CastRecordView, Option<ErasedHandler>);
+
+impl Record for CastArcRecord {
+    fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn erased(&self) -> Option<ErasedHandler> {
+        self.1
+    }
+}
+
+impl CastArcRecord {
+    pub fn new<S, T>(handler: CastArcHandler<T>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self::optional::<S, T>(Some(handler))
+    }
+
+    pub fn optional<S, T>(handler: Option<CastArcHandler<T>>) -> Self
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        Self(CastRecordView::new::<S, T>(), handler.map(erase))
+    }
+
+    pub fn view(&self) -> CastRecordView {
+        self.0
+    }
+
+    fn handler<T>(&self) -> Option<CastArcHandler<T>>
+    where
+        T: ?Sized + 'static,
+    {
+        // The handler was erased from a `CastArcHandler<T>` for this record's target.
+        unsafe { restore::<T, _>(self.0, self.1) }
+    }
+}
+
+pub struct CastInterfaceRecord {
+    id: InterfaceId,
+    target: TypeId,
+    target_name: &'static str,
+    query_ref: unsafe fn(&CastRefRecord, &dyn Any, *mut ()) -> bool,
+    query_mut: unsafe fn(&CastMutRecord, &mut dyn Any, *mut ()) -> bool,
+    query_ptr: fn(&CastRefRecord, &dyn Any) -> *const (),
+}
+
+impl CastInterfaceRecord {
+    pub fn new<T>() -> Self
+    where
+        T: CastInterface + ?Sized + 'static,
+    {
+        Self {
+            id: T::ID,
+            target: TypeId::of::<T>(),
+            target_name: type_name::<T>(),
+            query_ref: query_ref_as::<T>,
+            query_mut: query_mut_as::<T>,
+            query_ptr: query_ptr_as::<T>,
+        }
+    }
+
+    pub fn id(&self) -> InterfaceId {
+        self.id
+    }
+
+    pub fn target_name(&self) -> &'static str {
+        self.target_name
+    }
+}
+
+#[derive(Default)]
+pub struct CastRefRegistry(RecordMap<CastRefRecord>);
+
+impl CastRefRegistry {
+    pub fn new() -> Self {
+        #[cfg(all(feature = "strict-registry", debug_assertions))]
+        CastValidationReport::collect(CastRecordKind::Ref, submitted::<CastRefRecord>())
+            .assert_valid();
+
+        Self(RecordMap::collect(submitted::<CastRefRecord>()))
+    }
+
+    pub fn register(&self, record: CastRefRecord) {
+        self.0.register(record);
+    }
+
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&CastRecordView) -> bool,
+    {
+        Self(self.0.filter(f))
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = CastRecordView> {
+        self.0.views().into_iter()
+    }
+
+    pub fn implementors_of<T>(&self) -> impl Iterator<Item = CastRecordView>
+    where
+        T: ?Sized + 'static,
+    {
+        let target = TypeId::of::<T>();
+
+        self.records().filter(move |view| view.target == target)
+    }
+
+    pub fn targets_of<S>(&self, from: &S) -> impl Iterator<Item = CastRecordView>
+    where
+        S: Cast + ?Sized + 'static,
+    {
+        let source = from.cast_type_id();
+
+        self.records().filter(move |view| view.source == source)
+    }
+
+    pub fn can_cast<S, T>(&self) -> bool
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        self.0.contains(&(TypeId::of::<T>(), TypeId::of::<S>()))
+    }
+
+    pub fn cast_from_ref<'a, S, T>(&self, from: &'a S) -> Option<&'a T>
+    where
+        S: Cast + ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        self.try_cast_from_ref(from).ok()
+    }
+
+    pub fn try_cast_from_ref<'a, S, T>(&self, from: &'a S) -> Result<&'a T, CastError>
+    where
+        S: Cast + ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        self.cast(from.cast_type_name(), from.cast_as_any_ref())
+    }
+
+    pub fn cast_any_ref<'a, T>(&self, from: &'a dyn Any) -> Option<&'a T>
+    where
+        T: ?Sized + 'static,
+    {
+        self.try_cast_any_ref(from).ok()
+    }
+
+    pub fn try_cast_any_ref<'a, T>(&self, from: &'a dyn Any) -> Result<&'a T, CastError>
+    where
+        T: ?Sized + 'static,
+    {
+        self.cast(type_name::<dyn Any>(), from)
+    }
+
+    fn cast<'a, T>(&self, source: &'static str, from: &'a dyn Any) -> Result<&'a T, CastError>
+    where
+        T: ?Sized + 'static,
+    {
+        let type_id = from.type_id();
+
+        if TypeId::of::<T>() == type_id {
+            return Ok(unsafe { downcast_ref(from) });
+        }
+
+        let rec = self.0.get(&(TypeId::of::<T>(), type_id));
+
+        if let Some(cast) = rec.and_then(|rec| rec.handler::<T>()) {
+            if let Some(item) = (cast)(from) {
+                return Ok(item);
+            }
+
+            return Err(CastError::new(
+                source,
+                type_name::<T>(),
+                CastErrorKind::Mismatch,
+            ));
+        }
+
+        Err(CastError::new(
+            source,
+            type_name::<T>(),
+            self.miss_kind::<T>(),
+        ))
+    }
+
+    // A target that is itself a registered concrete type is a downcast to the wrong type.
+    fn miss_kind<T>(&self) -> CastErrorKind
+    where
+        T: ?Sized + 'static,
+    {
+        match self.0.has_source(TypeId::of::<T>()) {
+            true => CastErrorKind::Mismatch,
+            false => CastErrorKind::Unregistered,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CastMutRegistry(RecordMap<CastMutRecord>);
+
+impl CastMutRegistry {
+    pub fn new() -> Self {
+        #[cfg(all(feature = "strict-registry", debug_assertions))]
+        CastValidationReport::collect(CastRecordKind::Mut, submitted::<CastMutRecord>())
+            .assert_valid();
+
+        #[cfg(all(feature = "strict-registry", debug_assertions))]
+        CastParityReport::new(
+            &CastRefRegistry(RecordMap::collect(submitted::<CastRefRecord>())),
+            &CastMutRegistry(RecordMap::collect(submitted::<CastMutRecord>())),
+        )
+        .assert_valid();
+
+        Self(RecordMap::collect(submitted::<CastMutRecord>()))
+    }
+
+    pub fn register(&self, record: CastMutRecord) {
+        self.0.register(record);
+    }
+
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&CastRecordView) -> bool,
+    {
+        Self(self.0.filter(f))
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = CastRecordView> {
+        self.0.views().into_iter()
+    }
+
+    pub fn implementors_of<T>(&self) -> impl Iterator<Item = CastRecordView>
+    where
+        T: ?Sized + 'static,
+    {
+        let target = TypeId::of::<T>();
+
+        self.records().filter(move |view| view.target == target)
+    }
+
+    pub fn targets_of<S>(&self, from: &S) -> impl Iterator<Item = CastRecordView>
+    where
+        S: Cast + ?Sized + 'static,
+    {
+        let source = from.cast_type_id();
+
+        self.records().filter(move |view| view.source == source)
+    }
+
+    pub fn can_cast<S, T>(&self) -> bool
+    where
+        S: 'static,
+        T: ?Sized + 'static,
+    {
+        self.0.contains(&(TypeId::of::<T>(), TypeId::of::<S>()))
+    }
+
+    pub fn cast_from_mut<'a, S, T>(&self, from: &'a mut S) -> Option<&'a mut T>
+    where
+        S: Cast + ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        self.try_cast_from_mut(from).ok()
+    }
+
+    pub fn try_cast_from_mut<'a, S, T>(&self, from: &'a mut S) -> Result<&'a mut T, CastError>
+    where
+        S: Cast + ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        self.cast((*from).cast_type_name(), (*from).cast_as_any_mut())
+    }
+
+    pub fn cast_any_mut<'a, T>(&self, from: &'a mut dyn Any) -> Option<&'a mut T>
+    where
+        T: ?Sized + 'static,
+    {
+        self.try_cast_any_mut(from).ok()
+    }
+
+    pub fn try_cast_any_mut<'a, T>(&self, from: &'a mut dyn Any) -> Result<&'a mut T, CastError>
+    where
+        T: ?Sized + 'static,
+    {
+        self.cast(type_name::<dyn Any>(), from)
+    }
+
+    fn cast<'a, T>(
+        &self,
+        source: &'static str,
+        from: &'a mut dyn Any,
+    ) -> Result<&'a mut T, CastError>
+    where
+        T: ?Sized + 'static,
+    {
+        let type_id = (from as &dyn Any).type_id();
+
+        if TypeId::of::<T>() == type_id {
+            return Ok(unsafe { downcast_mut(from) });
+        }
+
+        let rec = self.0.get(&(TypeId::of::<T>(), type_id));
+
+        if let Some(cast) = rec.and_then(|rec| rec.handler::<T>()) {
+            if let Some(item) = (cast)(from) {
+                return Ok(item);
+            }
+
+            return Err(CastError::new(
+                source,
+                type_name::<T>(),
+                CastErrorKind::Mismatch,
+            ));
+        }
+
+        Err(CastError::new(
+            source,
+            type_name::<T>(),
+            self.miss_kind::<T>(),
+        ))
+    }
+
+    // A target that is itself a registered concrete type is a downcast to the wrong type.
+    fn miss_kind<T>(&self) -> CastErrorKind
+    where
+        T: ?Sized + 'static,
+    {
+        match self.0.has_source(TypeId::of::<T>()) {
+            true => CastErrorKind::Mismatch,
+            false => CastErrorKind::Unregistered,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CastBoxRegistry(RecordMap<CastBoxRecord>);
+
+impl CastBoxRegistry {
+    pub fn new() -> Self {
+        #[cfg(all(feature = "strict-registry", debug_assertions))]
+        CastValidationReport::collect(CastRecordKind::Box, submitted::<CastBoxRecord>())
+            .assert_valid();
+
+        Self(RecordMap::collect(submitted::<CastBoxRecord>()))
+    }
+
+    pub fn register(&self, record: CastBoxRecord) {
+        self.0.register(record);
+    }
+
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&CastRecordView) -> bool,
+    {
+        Self(self.0.filter(f))
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = CastRecordView> {
+        self.0.views().into_iter()
+    }
+
+    pub fn cast_from_box<S, T>(&self, from: Box<S>) -> Result<Box<T>, Box<S>>
+    where
+        S: Cast + ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        let type_id = (*from).cast_type_id();
+
+        let rec = self.0.get(&(TypeId::of::<T>(), type_id));
+
+        match rec.and_then(|rec| rec.handler::<T>()) {
+            Some(cast) => rebox(from, |item| (cast)(item.cast_as_any_mut())),
+            None => Err(from),
+        }
+    }
+
+    pub fn cast_any_box<T>(&self, from: Box<dyn Any>) -> Result<Box<T>, Box<dyn Any>>
+    where
+        T: ?Sized + 'static,
+    {
+        let type_id = (*from).type_id();
+
+        let rec = self.0.get(&(TypeId::of::<T>(), type_id));
+
+        match rec.and_then(|rec| rec.handler::<T>()) {
+            Some(cast) => rebox(from, cast),
+            None => Err(from),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CastRcRegistry(RecordMap<CastRcRecord>);
+
+impl CastRcRegistry {
+    pub fn new() -> Self {
+        #[cfg(all(feature = "strict-registry", debug_assertions))]
+        CastValidationReport::collect(CastRecordKind::Rc, submitted::<CastRcRecord>())
+            .assert_valid();
+
+        Self(RecordMap::collect(submitted::<CastRcRecord>()))
+    }
+
+    pub fn register(&self, record: CastRcRecord) {
+        self.0.register(record);
+    }
+
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&CastRecordView) -> bool,
+    {
+        Self(self.0.filter(f))
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = CastRecordView> {
+        self.0.views().into_iter()
+    }
+
+    pub fn cast_from_rc<S, T>(&self, from: Rc<S>) -> Result<Rc<T>, Rc<S>>
+    where
+        S: Cast + ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        let type_id = (*from).cast_type_id();
+
+        let rec = self.0.get(&(TypeId::of::<T>(), type_id));
+
+        if let Some(cast) = rec.and_then(|rec| rec.handler::<T>()) {
+            // The handler only gets a second reference, so `from` survives a refused cast.
+            return match (cast)(CastAsAny::cast_as_any_rc(from.clone())) {
+                Ok(item) => Ok(item),
+                Err(_) => Err(from),
+            };
+        }
+
+        Err(from)
+    }
+}
+
+#[derive(Default)]
+pub struct CastArcRegistry(RecordMap<CastArcRecord>);
+
+impl CastArcRegistry {
+    pub fn new() -> Self {
+        #[cfg(all(feature = "strict-registry", debug_assertions))]
+        CastValidationReport::collect(CastRecordKind::Arc, submitted::<CastArcRecord>())
+            .assert_valid();
+
+        Self(RecordMap::collect(submitted::<CastArcRecord>()))
+    }
+
+    pub fn register(&self, record: CastArcRecord) {
+        self.0.register(record);
+    }
+
+    pub fn filter<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&CastRecordView) -> bool,
+    {
+        Self(self.0.filter(f))
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = CastRecordView> {
+        self.0.views().into_iter()
+    }
+
+    pub fn cast_from_arc<S, T>(&self, from: Arc<S>) -> Result<Arc<T>, Arc<S>>
+    where
+        S: Cast + Send + Sync + ?Sized + 'static,
+        T: ?Sized + 'static,
+    {
+        let type_id = (*from).cast_type_id();
+
+        let rec = self.0.get(&(TypeId::of::<T>(), type_id));
+
+        if let Some(cast) = rec.and_then(|rec| rec.handler::<T>()) {
+            // The handler only gets a second reference, so `from` survives a refused cast.
+            return match (cast)(CastAsAny::cast_as_any_arc(from.clone())) {
+                Ok(item) => Ok(item),
+                Err(_) => Err(from),
+            };
+        }
+
+        Err(from)
+    }
+}
+
+#[derive(Default)]
+pub struct CastInterfaceRegistry(RwLock<InterfaceMap>);
+
+impl CastInterfaceRegistry {
+    pub fn new() -> Self {
+        let registry = Self::default();
+
+        for record in submitted::<CastInterfaceRecord>() {
+            registry.insert(record);
+        }
+
+        registry
+    }
+
+    pub fn register(&self, record: CastInterfaceRecord) {
+        let mut map = self.0.write();
+        let registered = map
+            .get(&record.id)
+            .is_some_and(|rec| rec.target == record.target);
+
+        if !registered {
+            map.insert(record.id, Box::leak(Box::new(record)));
+        }
+    }
+
+    pub fn get(&self, id: InterfaceId) -> Option<&'static CastInterfaceRecord> {
+        self.0.read().get(&id).copied()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &'static CastInterfaceRecord> {
+        self.0
+            .read()
+            .values()
+            .copied()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn insert(&self, record: &'static CastInterfaceRecord) {
+        self.0.write().insert(record.id, record);
+    }
+}
+
+#[cfg(feature = "static-registry")]
+pub struct CastTable {
+    refs: &'static [fn() -> CastRecordSet<CastRefRecord>],
+    muts: &'static [fn() -> CastRecordSet<CastMutRecord>],
+    boxes: &'static [fn() -> CastRecordSet<CastBoxRecord>],
+    rcs: &'static [fn() -> CastRecordSet<CastRcRecord>],
+    arcs: &'static [fn() -> CastRecordSet<CastArcRecord>],
+    interfaces: &'static [fn() -> CastRecordSet<CastInterfaceRecord>],
+}
+
+#[cfg(feature = "static-registry")]
+impl CastTable {
+    pub const fn new(
+        refs: &'static [fn() -> CastRecordSet<CastRefRecord>],
+        muts: &'static [fn() -> CastRecordSet<CastMutRecord>],
+        boxes: &'static [fn() -> CastRecordSet<CastBoxRecord>],
+        rcs: &'static [fn() -> CastRecordSet<CastRcRecord>],
+        arcs: &'static [fn() -> CastRecordSet<CastArcRecord>],
+        interfaces: &'static [fn() -> CastRecordSet<CastInterfaceRecord>],
+    ) -> Self {
+        Self {
+            refs,
+            muts,
+            boxes,
+            rcs,
+            arcs,
+            interfaces,
+        }
+    }
+}
+
+pub struct CastExports {
+    refs: Vec<&'static CastRefRecord>,
+    muts: Vec<&'static CastMutRecord>,
+    interfaces: Vec<&'static CastInterfaceRecord>,
+}
+
+impl CastExports {
+    fn collect() -> Self {
+        Self {
+            refs: submitted::<CastRefRecord>().collect(),
+            muts: submitted::<CastMutRecord>().collect(),
+            interfaces: submitted::<CastInterfaceRecord>().collect(),
+        }
+    }
+
+    pub fn ref_records(&self) -> usize {
+        self.refs.len()
+    }
+
+    pub fn mut_records(&self) -> usize {
+        self.muts.len()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = CastRecordView> + '_ {
+        let refs = self.refs.iter().map(|rec| rec.view());
+
+        refs.chain(self.muts.iter().map(|rec| rec.view()))
+    }
+}
+
+#[must_use = "imported casts are removed again when the import is dropped"]
+pub struct CastImport {
+    refs: Vec<&'static CastRefRecord>,
+    muts: Vec<&'static CastMutRecord>,
+}
+
+impl CastImport {
+    pub fn ref_records(&self) -> usize {
+        self.refs.len()
+    }
+
+    pub fn mut_records(&self) -> usize {
+        self.muts.len()
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = CastRecordView> + '_ {
+        let refs = self.refs.iter().map(|rec| rec.view());
+
+        refs.chain(self.muts.iter().map(|rec| rec.view()))
+    }
+}
+
+impl Drop for CastImport {
+    fn drop(&mut self) {
+        for rec in self.refs.drain(..) {
+            CAST_REF_REGISTRY.0.remove(rec);
+        }
+
+        for rec in self.muts.drain(..) {
+            CAST_MUT_REGISTRY.0.remove(rec);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CastInitStats {
+    ref_records: usize,
+    mut_records: usize,
+    box_records: usize,
+    rc_records: usize,
+    arc_records: usize,
+    elapsed: Option<Duration>,
+    performed_init: bool,
+}
+
+impl CastInitStats {
+    fn collect<F>(f: F) -> Self
+    where
+        F: FnOnce(),
+    {
+        let performed_init = !is_initialized();
+        let elapsed = timed(f);
+
+        Self {
+            ref_records: CAST_REF_REGISTRY.0.len(),
+            mut_records: CAST_MUT_REGISTRY.0.len(),
+            box_records: CAST_BOX_REGISTRY.0.len(),
+            rc_records: CAST_RC_REGISTRY.0.len(),
+            arc_records: CAST_ARC_REGISTRY.0.len(),
+            elapsed,
+            performed_init,
+        }
+    }
+
+    pub fn ref_records(&self) -> usize {
+        self.ref_records
+    }
+
+    pub fn mut_records(&self) -> usize {
+        self.mut_records
+    }
+
+    pub fn box_records(&self) -> usize {
+        self.box_records
+    }
+
+    pub fn rc_records(&self) -> usize {
+        self.rc_records
+    }
+
+    pub fn arc_records(&self) -> usize {
+        self.arc_records
+    }
+
+    pub fn total_records(&self) -> usize {
+        self.ref_records + self.mut_records + self.box_records + self.rc_records + self.arc_records
+    }
+
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+
+    /// Whether this call built the registries, as opposed to finding them already built.
+    pub fn performed_init(&self) -> bool {
+        self.performed_init
+    }
+}
 
-pub fn cast_from_ref<S, T>(from: &S) -> Option<&T>
+impl fmt::Display for CastInitStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} cast records (ref: {}, mut: {}, box: {}, rc: {}, arc: {})",
+            self.total_records(),
+            self.ref_records,
+            self.mut_records,
+            self.box_records,
+            self.rc_records,
+            self.arc_records,
+        )?;
+
+        match self.elapsed {
+            Some(elapsed) => write!(f, " in {:?}", elapsed),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CastRecordKind {
+    Ref,
+    Mut,
+    Box,
+    Rc,
+    Arc,
+}
+
+impl fmt::Display for CastRecordKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CastRecordKind::Ref => write!(f, "ref"),
+            CastRecordKind::Mut => write!(f, "mut"),
+            CastRecordKind::Box => write!(f, "box"),
+            CastRecordKind::Rc => write!(f, "rc"),
+            CastRecordKind::Arc => write!(f, "arc"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CastDuplicate {
+    kind: CastRecordKind,
+    view: CastRecordView,
+    count: usize,
+}
+
+impl CastDuplicate {
+    pub fn kind(&self) -> CastRecordKind {
+        self.kind
+    }
+
+    pub fn view(&self) -> CastRecordView {
+        self.view
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl fmt::Display for CastDuplicate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} cast `{}` registered {} times",
+            self.kind, self.view, self.count
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CastValidationReport {
+    duplicates: Vec<CastDuplicate>,
+    parity: CastParityReport,
+}
+
+impl CastValidationReport {
+    fn collect<R, I>(kind: CastRecordKind, iter: I) -> Self
+    where
+        R: Record,
+        I: IntoIterator<Item = &'static R>,
+    {
+        let mut seen = HashMap::<_, Vec<&'static R>, BuildHasherDefault<TypeIdHasher>>::default();
+        let mut order = Vec::new();
+
+        for rec in iter.into_iter().filter(|rec| rec.is_supported()) {
+            let key = rec.view().key();
+
+            let recs = seen.entry(key).or_insert_with(|| {
+                order.push(key);
+
+                Vec::new()
+            });
+
+            recs.push(rec);
+        }
+
+        let duplicates = order
+            .into_iter()
+            .map(|key| &seen[&key])
+            .filter(|recs| recs.len() > 1)
+            .map(|recs| CastDuplicate {
+                kind,
+                view: recs[0].view(),
+                count: recs.len(),
+            })
+            .collect();
+
+        Self {
+            duplicates,
+            parity: CastParityReport::default(),
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.duplicates.extend(other.duplicates);
+        self.parity.ref_only.extend(other.parity.ref_only);
+        self.parity.mut_only.extend(other.parity.mut_only);
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.duplicates.is_empty() && self.parity.is_valid()
+    }
+
+    pub fn duplicates(&self) -> impl Iterator<Item = &CastDuplicate> {
+        self.duplicates.iter()
+    }
+
+    pub fn parity(&self) -> &CastParityReport {
+        &self.parity
+    }
+
+    pub fn assert_valid(&self) {
+        if !self.is_valid() {
+            panic!("invalid cast registry:\n{}", self);
+        }
+    }
+}
+
+impl fmt::Display for CastValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for dup in &self.duplicates {
+            writeln!(f, "{}", dup)?;
+        }
+
+        write!(f, "{}", self.parity)
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CastParityReport {
+    ref_only: Vec<CastRecordView>,
+    mut_only: Vec<CastRecordView>,
+}
+
+impl CastParityReport {
+    pub fn new(refs: &CastRefRegistry, muts: &CastMutRegistry) -> Self {
+        let mut ref_only = refs
+            .records()
+            .filter(|view| !muts.0.contains(&view.key()))
+            .collect::<Vec<_>>();
+        let mut mut_only = muts
+            .records()
+            .filter(|view| !refs.0.contains(&view.key()))
+            .collect::<Vec<_>>();
+
+        ref_only.sort_by_key(|view| (view.source_name, view.target_name));
+        mut_only.sort_by_key(|view| (view.source_name, view.target_name));
+
+        Self { ref_only, mut_only }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.ref_only.is_empty() && self.mut_only.is_empty()
+    }
+
+    pub fn ref_only(&self) -> impl Iterator<Item = &CastRecordView> {
+        self.ref_only.iter()
+    }
+
+    pub fn mut_only(&self) -> impl Iterator<Item = &CastRecordView> {
+        self.mut_only.iter()
+    }
+
+    pub fn assert_valid(&self) {
+        if !self.is_valid() {
+            panic!("cast registry ref/mut mismatch:\n{}", self);
+        }
+    }
+}
+
+impl fmt::Display for CastParityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for view in &self.ref_only {
+            writeln!(f, "`{}` is registered for ref but not mut", view)?;
+        }
+
+        for view in &self.mut_only {
+            writeln!(f, "`{}` is registered for mut but not ref", view)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "linkme", feature = "static-registry"))]
+trait Submitted: Sized + 'static {
+    fn constructors() -> &'static [fn() -> CastRecordSet<Self>];
+
+    fn submitted() -> &'static [Self];
+}
+
+#[cfg(any(feature = "linkme", feature = "static-registry"))]
+impl Submitted for CastRefRecord {
+    fn constructors() -> &'static [fn() -> CastRecordSet<Self>] {
+        #[cfg(feature = "static-registry")]
+        return cast_table().refs;
+
+        #[cfg(not(feature = "static-registry"))]
+        return &CAST_REF_RECORDS;
+    }
+
+    fn submitted() -> &'static [Self] {
+        static RECORDS: Lazy<Vec<CastRefRecord>> = Lazy::new(|| {
+            CastRefRecord::constructors()
+                .iter()
+                .flat_map(|set| set().0)
+                .collect()
+        });
+
+        &RECORDS
+    }
+}
+
+#[cfg(any(feature = "linkme", feature = "static-registry"))]
+impl Submitted for CastMutRecord {
+    fn constructors() -> &'static [fn() -> CastRecordSet<Self>] {
+        #[cfg(feature = "static-registry")]
+        return cast_table().muts;
+
+        #[cfg(not(feature = "static-registry"))]
+        return &CAST_MUT_RECORDS;
+    }
+
+    fn submitted() -> &'static [Self] {
+        static RECORDS: Lazy<Vec<CastMutRecord>> = Lazy::new(|| {
+            CastMutRecord::constructors()
+                .iter()
+                .flat_map(|set| set().0)
+                .collect()
+        });
+
+        &RECORDS
+    }
+}
+
+#[cfg(any(feature = "linkme", feature = "static-registry"))]
+impl Submitted for CastBoxRecord {
+    fn constructors() -> &'static [fn() -> CastRecordSet<Self>] {
+        #[cfg(feature = "static-registry")]
+        return cast_table().boxes;
+
+        #[cfg(not(feature = "static-registry"))]
+        return &CAST_BOX_RECORDS;
+    }
+
+    fn submitted() -> &'static [Self] {
+        static RECORDS: Lazy<Vec<CastBoxRecord>> = Lazy::new(|| {
+            CastBoxRecord::constructors()
+                .iter()
+                .flat_map(|set| set().0)
+                .collect()
+        });
+
+        &RECORDS
+    }
+}
+
+#[cfg(any(feature = "linkme", feature = "static-registry"))]
+impl Submitted for CastRcRecord {
+    fn constructors() -> &'static [fn() -> CastRecordSet<Self>] {
+        #[cfg(feature = "static-registry")]
+        return cast_table().rcs;
+
+        #[cfg(not(feature = "static-registry"))]
+        return &CAST_RC_RECORDS;
+    }
+
+    fn submitted() -> &'static [Self] {
+        static RECORDS: Lazy<Vec<CastRcRecord>> = Lazy::new(|| {
+            CastRcRecord::constructors()
+                .iter()
+                .flat_map(|set| set().0)
+                .collect()
+        });
+
+        &RECORDS
+    }
+}
+
+#[cfg(any(feature = "linkme", feature = "static-registry"))]
+impl Submitted for CastArcRecord {
+    fn constructors() -> &'static [fn() -> CastRecordSet<Self>] {
+        #[cfg(feature = "static-registry")]
+        return cast_table().arcs;
+
+        #[cfg(not(feature = "static-registry"))]
+        return &CAST_ARC_RECORDS;
+    }
+
+    fn submitted() -> &'static [Self] {
+        static RECORDS: Lazy<Vec<CastArcRecord>> = Lazy::new(|| {
+            CastArcRecord::constructors()
+                .iter()
+                .flat_map(|set| set().0)
+                .collect()
+        });
+
+        &RECORDS
+    }
+}
+
+#[cfg(any(feature = "linkme", feature = "static-registry"))]
+impl Submitted for CastInterfaceRecord {
+    fn constructors() -> &'static [fn() -> CastRecordSet<Self>] {
+        #[cfg(feature = "static-registry")]
+        return cast_table().interfaces;
+
+        #[cfg(not(feature = "static-registry"))]
+        return &CAST_INTERFACE_RECORDS;
+    }
+
+    fn submitted() -> &'static [Self] {
+        static RECORDS: Lazy<Vec<CastInterfaceRecord>> = Lazy::new(|| {
+            CastInterfaceRecord::constructors()
+                .iter()
+                .flat_map(|set| set().0)
+                .collect()
+        });
+
+        &RECORDS
+    }
+}
+
+trait Record: 'static {
+    fn view(&self) -> CastRecordView;
+
+    fn erased(&self) -> Option<ErasedHandler>;
+
+    fn handler_addr(&self) -> usize {
+        self.erased().map_or(0, |handler| handler as usize)
+    }
+
+    fn is_supported(&self) -> bool {
+        self.erased().is_some()
+    }
+}
+
+type ErasedHandler = fn();
+
+type InterfaceMap =
+    HashMap<InterfaceId, &'static CastInterfaceRecord, BuildHasherDefault<TypeIdHasher>>;
+
+type TableMap<R> = HashMap<TypeId, SourceTable<R>, BuildHasherDefault<TypeIdHasher>>;
+
+fn rebox<S, T, F>(from: Box<S>, cast: F) -> Result<Box<T>, Box<S>>
 where
-    S: Cast + ?Sized + 'static,
-    T: ?Sized + 'static,
+    S: ?Sized,
+    T: ?Sized,
+    F: FnOnce(&mut S) -> Option<&mut T>,
 {
-    CAST_REF_REGISTRY.cast_from_ref(from)
+    let layout = Layout::for_value(&*from);
+    let raw = Box::into_raw(from);
+    let item = cast(unsafe { &mut *raw }).map(|item| item as *mut T);
+
+    // The handler only borrowed `raw`; its result takes over the allocation only when it is the
+    // whole of it, otherwise the original box is handed back untouched.
+    match item {
+        Some(item) if is_whole(raw, item, layout) => Ok(unsafe { Box::from_raw(item) }),
+        _ => Err(unsafe { Box::from_raw(raw) }),
+    }
 }
 
-pub fn cast_from_mut<S, T>(from: &mut S) -> Option<&mut T>
+fn is_whole<S: ?Sized, T: ?Sized>(raw: *mut S, item: *mut T, layout: Layout) -> bool {
+    ptr::eq(item as *const (), raw as *const ()) && Layout::for_value(unsafe { &*item }) == layout
+}
+
+fn erase<H: Copy>(handler: H) -> ErasedHandler {
+    assert_eq!(mem::size_of::<H>(), mem::size_of::<ErasedHandler>());
+
+    // Every handler type is a plain `fn` pointer, so this only forgets its signature.
+    unsafe { mem::transmute_copy(&handler) }
+}
+
+unsafe fn restore<T, H>(view: CastRecordView, handler: Option<ErasedHandler>) -> Option<H>
 where
-    S: Cast + ?Sized + 'static,
     T: ?Sized + 'static,
+    H: Copy,
 {
-    CAST_MUT_REGISTRY.cast_from_mut(from)
+    if view.target != TypeId::of::<T>() {
+        return None;
+    }
+
+    handler.map(|handler| mem::transmute_copy(&handler))
 }
 
-pub struct CastRefRecord(TypeId, TypeId, Box<dyn Any + Sync>);
+#[derive(Default)]
+struct TypeIdHasher(u64);
 
-impl CastRefRecord {
-    pub fn new<S, T>(handler: CastRefHandler<T>) -> Self
+impl Hasher for TypeIdHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut buf = [0; 8];
+
+            buf[..chunk.len()].copy_from_slice(chunk);
+            self.0 = self.0.rotate_left(5) ^ u64::from_ne_bytes(buf);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = self.0.rotate_left(5) ^ n;
+    }
+}
+
+struct SourceTable<R: 'static>(Vec<(TypeId, &'static R)>);
+
+impl<R> Clone for SourceTable<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R: Record> SourceTable<R> {
+    fn get(&self, target: TypeId) -> Option<&'static R> {
+        self.0
+            .iter()
+            .find(|(id, _)| *id == target)
+            .map(|(_, rec)| *rec)
+    }
+
+    fn insert(&mut self, rec: &'static R) {
+        let target = rec.view().target;
+
+        match self.0.iter_mut().find(|(id, _)| *id == target) {
+            Some(entry) => entry.1 = rec,
+            None => self.0.push((target, rec)),
+        }
+    }
+}
+
+// Tables are keyed by source `TypeId` rather than reached through `CastAsAny`: that trait is
+// implemented for every `T` by one blanket impl, and Rust has no per-monomorphization statics, so
+// a hidden method has no per-type storage to return. Records are also only known at link time.
+struct RecordMap<R: 'static> {
+    tables: TableMap<R>,
+    // Runtime records are published as immutable snapshots, so a lookup only loads a pointer.
+    // Writers take the lock and swap in a new snapshot; replaced ones stay alive until the map is
+    // dropped since a lookup may still be reading them.
+    runtime: AtomicPtr<TableMap<R>>,
+    #[allow(clippy::vec_box)]
+    snapshots: RwLock<Vec<Box<TableMap<R>>>>,
+}
+
+impl<R: Record> RecordMap<R> {
+    fn collect<I>(iter: I) -> Self
     where
-        S: 'static,
-        T: ?Sized + 'static,
+        I: IntoIterator<Item = &'static R>,
     {
-        Self(TypeId::of::<T>(), TypeId::of::<S>(), Box::new(handler))
+        let mut tables = TableMap::default();
+
+        for rec in iter {
+            Self::insert_into(&mut tables, rec);
+        }
+
+        Self {
+            tables,
+            ..Self::default()
+        }
     }
-}
 
-pub struct CastMutRecord(TypeId, TypeId, Box<dyn Any + Sync>);
+    fn get(&self, key: &(TypeId, TypeId)) -> Option<&'static R> {
+        if let Some(runtime) = self.runtime() {
+            if let Some(rec) = Self::lookup(runtime, key) {
+                return Some(rec);
+            }
+        }
 
-impl CastMutRecord {
-    pub fn new<S, T>(handler: CastMutHandler<T>) -> Self
+        Self::lookup(&self.tables, key)
+    }
+
+    fn runtime(&self) -> Option<&TableMap<R>> {
+        // Published snapshots are owned by `snapshots`, which only grows until the map is dropped.
+        unsafe { self.runtime.load(Ordering::Acquire).as_ref() }
+    }
+
+    fn update<F>(&self, f: F) -> bool
     where
-        S: 'static,
-        T: ?Sized + 'static,
+        F: FnOnce(&mut TableMap<R>) -> bool,
     {
-        Self(TypeId::of::<T>(), TypeId::of::<S>(), Box::new(handler))
+        let mut snapshots = self.snapshots.write();
+        let mut runtime = self.runtime().cloned().unwrap_or_default();
+
+        if !f(&mut runtime) {
+            return false;
+        }
+
+        let mut runtime = Box::new(runtime);
+
+        self.runtime.store(&mut *runtime, Ordering::Release);
+        snapshots.push(runtime);
+
+        true
     }
-}
 
-#[derive(Default)]
-pub struct CastRefRegistry(HashMap<(TypeId, TypeId), &'static CastRefRecord>);
+    fn contains(&self, key: &(TypeId, TypeId)) -> bool {
+        self.get(key).is_some()
+    }
 
-impl CastRefRegistry {
-    pub fn new() -> Self {
-        let mut map = HashMap::new();
+    fn has_source(&self, source: TypeId) -> bool {
+        let runtime = self
+            .runtime()
+            .is_some_and(|runtime| runtime.contains_key(&source));
 
-        for rec in inventory::iter::<CastRefRecord> {
-            map.insert((rec.0, rec.1), rec);
+        runtime || self.tables.contains_key(&source)
+    }
+
+    fn register(&self, rec: R) {
+        if !rec.is_supported() {
+            return;
         }
 
-        Self(map)
+        let key = rec.view().key();
+
+        self.update(|runtime| {
+            let current = Self::lookup(runtime, &key).or_else(|| Self::lookup(&self.tables, &key));
+
+            // Registering the same handler again must not leak another copy of the record.
+            if current.is_some_and(|cur| cur.handler_addr() == rec.handler_addr()) {
+                return false;
+            }
+
+            Self::insert_into(runtime, Box::leak(Box::new(rec)));
+
+            true
+        });
     }
 
-    pub fn cast_from_ref<'a, S, T>(&self, from: &'a S) -> Option<&'a T>
+    fn import(&self, rec: &'static R) -> bool {
+        let key = (rec.view().target, rec.view().source);
+
+        if !rec.is_supported() || Self::lookup(&self.tables, &key).is_some() {
+            return false;
+        }
+
+        self.update(|runtime| {
+            if Self::lookup(runtime, &key).is_some() {
+                return false;
+            }
+
+            Self::insert_into(runtime, rec);
+
+            true
+        })
+    }
+
+    fn remove(&self, rec: &'static R) {
+        let source = rec.view().source;
+
+        self.update(|runtime| {
+            let table = match runtime.get_mut(&source) {
+                Some(table) => table,
+                None => return false,
+            };
+
+            table.0.retain(|(_, entry)| !ptr::eq(*entry, rec));
+
+            if table.0.is_empty() {
+                runtime.remove(&source);
+            }
+
+            true
+        });
+    }
+
+    fn lookup(map: &TableMap<R>, key: &(TypeId, TypeId)) -> Option<&'static R> {
+        map.get(&key.1)?.get(key.0)
+    }
+
+    fn insert_into(map: &mut TableMap<R>, rec: &'static R) {
+        if rec.is_supported() {
+            let source = rec.view().source;
+
+            map.entry(source)
+                .or_insert_with(|| SourceTable(Vec::new()))
+                .insert(rec);
+        }
+    }
+
+    fn filter<F>(&self, mut f: F) -> Self
     where
-        S: Cast + ?Sized + 'static,
-        T: ?Sized + 'static,
+        F: FnMut(&CastRecordView) -> bool,
     {
-        let from = from.cast_as_any_ref();
-        let type_id = from.type_id();
+        let iter = self.records().into_iter().filter(|rec| f(&rec.view()));
 
-        if let Some(rec) = self.0.get(&(TypeId::of::<T>(), type_id)) {
-            let item = (&*rec.2) as &dyn Any;
+        Self::collect(iter)
+    }
 
-            if let Some(cast) = item.downcast_ref::<CastRefHandler<T>>() {
-                return (cast)(from);
-            }
+    fn records(&self) -> Vec<&'static R> {
+        let runtime = self
+            .runtime()
+            .into_iter()
+            .flat_map(|runtime| runtime.values());
+        let tables = self.tables.values().chain(runtime);
+        let mut records = TableMap::default();
+
+        for rec in tables.flat_map(|table| table.0.iter().map(|(_, rec)| *rec)) {
+            Self::insert_into(&mut records, rec);
         }
 
-        None
+        records
+            .values()
+            .flat_map(|table| table.0.iter().map(|(_, rec)| *rec))
+            .collect()
     }
-}
 
-#[derive(Default)]
-pub struct CastMutRegistry(HashMap<(TypeId, TypeId), &'static CastMutRecord>);
+    fn len(&self) -> usize {
+        self.records().len()
+    }
 
-impl CastMutRegistry {
-    pub fn new() -> Self {
-        let mut map = HashMap::new();
+    fn views(&self) -> Vec<CastRecordView> {
+        self.records().into_iter().map(|rec| rec.view()).collect()
+    }
+}
 
-        for rec in inventory::iter::<CastMutRecord> {
-            map.insert((rec.0, rec.1), rec);
+impl<R> Default for RecordMap<R> {
+    fn default() -> Self {
+        Self {
+            tables: TableMap::default(),
+            runtime: AtomicPtr::new(ptr::null_mut()),
+            snapshots: RwLock::new(Vec::new()),
         }
+    }
+}
 
-        Self(map)
+#[cfg(feature = "std")]
+struct LocalRegistry<T: 'static> {
+    key: &'static LocalKey<Cell<*const T>>,
+    prev: *const T,
+}
+
+#[cfg(feature = "std")]
+impl<T> LocalRegistry<T> {
+    fn install(key: &'static LocalKey<Cell<*const T>>, registry: &T) -> Self {
+        let prev = key.with(|local| local.replace(registry));
+
+        Self { key, prev }
     }
 
-    pub fn cast_from_mut<'a, S, T>(&self, from: &'a mut S) -> Option<&'a mut T>
+    fn with<F, R>(key: &'static LocalKey<Cell<*const T>>, global: &T, f: F) -> R
     where
-        S: Cast + ?Sized + 'static,
-        T: ?Sized + 'static,
+        F: FnOnce(&T) -> R,
     {
-        let from = (*from).cast_as_any_mut();
-        let type_id = (from as &dyn Any).type_id();
-
-        if let Some(rec) = self.0.get(&(TypeId::of::<T>(), type_id)) {
-            let item = (&*rec.2) as &dyn Any;
+        let local = key.with(Cell::get);
 
-            if let Some(cast) = item.downcast_ref::<CastMutHandler<T>>() {
-                return (cast)(from);
-            }
+        // The pointer is only set while `install`'s guard, and therefore the
+        // borrowed registry, is alive on this thread.
+        match unsafe { local.as_ref() } {
+            Some(registry) => f(registry),
+            None => f(global),
         }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Drop for LocalRegistry<T> {
+    fn drop(&mut self) {
+        let prev = self.prev;
 
-        None
+        self.key.with(|local| local.set(prev));
     }
 }
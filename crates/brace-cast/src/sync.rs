@@ -0,0 +1,68 @@
+#[cfg(feature = "std")]
+pub use self::std_impl::{Lazy, RwLock};
+
+#[cfg(not(feature = "std"))]
+pub use self::spin_impl::{Lazy, RwLock};
+
+#[cfg(feature = "std")]
+mod std_impl {
+    use std::sync::{PoisonError, RwLockReadGuard, RwLockWriteGuard};
+
+    pub use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default)]
+    pub struct RwLock<T>(std::sync::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        pub fn new(value: T) -> Self {
+            Self(std::sync::RwLock::new(value))
+        }
+
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod spin_impl {
+    use core::ops::Deref;
+
+    use spin::Once;
+
+    pub use spin::RwLock;
+
+    pub struct Lazy<T> {
+        cell: Once<T>,
+        init: fn() -> T,
+    }
+
+    impl<T> Lazy<T> {
+        pub const fn new(init: fn() -> T) -> Self {
+            Self {
+                cell: Once::new(),
+                init,
+            }
+        }
+
+        pub fn force(this: &Self) -> &T {
+            this.cell.call_once(this.init)
+        }
+
+        pub fn get(this: &Self) -> Option<&T> {
+            this.cell.get()
+        }
+    }
+
+    impl<T> Deref for Lazy<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            Self::force(self)
+        }
+    }
+}